@@ -0,0 +1,108 @@
+// =======================================================
+// Project: GatedChess
+// File: board/mod.rs
+// Description: Defines board creation and modifications.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+pub mod square;
+pub use square::Square;
+
+use crate::config::BOARD_SIZE;
+use crate::pieces::{Piece, PieceType}; // Import board size constant
+
+/// Back rank of standard chess, from the a-file to the h-file.
+pub const STANDARD_BACK_RANK: [PieceType; BOARD_SIZE] = [
+    PieceType::Rook,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Queen,
+    PieceType::King,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::Rook,
+];
+
+/// Number of Chess960 starting positions.
+pub const CHESS960_POSITIONS: u16 = 960;
+
+pub fn create_board() -> [[Square; BOARD_SIZE]; BOARD_SIZE] {
+    create_board_with(STANDARD_BACK_RANK)
+}
+
+/// A starting board with both sides' pieces laid out like `back_rank`,
+/// mirrored for Black, behind a full rank of pawns.
+pub fn create_board_with(back_rank: [PieceType; BOARD_SIZE]) -> [[Square; BOARD_SIZE]; BOARD_SIZE] {
+    let mut board = [[Square::new(); BOARD_SIZE]; BOARD_SIZE];
+
+    // Place White pawns
+    for square in board[1].iter_mut() {
+        square.piece = Some(Piece::new(
+            crate::pieces::PieceType::Pawn,
+            crate::pieces::Color::White,
+        ));
+    }
+
+    // Place Black pawns
+    for square in board[6].iter_mut() {
+        square.piece = Some(Piece::new(
+            crate::pieces::PieceType::Pawn,
+            crate::pieces::Color::Black,
+        ));
+    }
+
+    // Place other pieces
+    for (i, &kind) in back_rank.iter().enumerate() {
+        board[0][i].piece = Some(Piece::new(kind, crate::pieces::Color::White));
+        board[7][i].piece = Some(Piece::new(kind, crate::pieces::Color::Black));
+    }
+
+    board
+}
+
+/// Back rank of Chess960 position `number`, counted the usual way from 0 to
+/// 959, where 518 is the standard layout. Bishops go on opposite colours
+/// and the king between the rooks.
+pub fn chess960_back_rank(number: u16) -> [PieceType; BOARD_SIZE] {
+    const KNIGHTS: [(usize, usize); 10] = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ];
+
+    let mut rank: [Option<PieceType>; BOARD_SIZE] = [None; BOARD_SIZE];
+    let mut n = (number % CHESS960_POSITIONS) as usize;
+    rank[n % 4 * 2 + 1] = Some(PieceType::Bishop);
+    n /= 4;
+    rank[n % 4 * 2] = Some(PieceType::Bishop);
+    n /= 4;
+
+    // The rest fill whatever files are still empty, left to right
+    let empty = |rank: &[Option<PieceType>; BOARD_SIZE]| -> Vec<usize> {
+        (0..BOARD_SIZE).filter(|&col| rank[col].is_none()).collect()
+    };
+    rank[empty(&rank)[n % 6]] = Some(PieceType::Queen);
+    n /= 6;
+    let (first, second) = KNIGHTS[n];
+    let files = empty(&rank);
+    rank[files[first]] = Some(PieceType::Knight);
+    rank[files[second]] = Some(PieceType::Knight);
+    for (file, kind) in empty(&rank)
+        .into_iter()
+        .zip([PieceType::Rook, PieceType::King, PieceType::Rook])
+    {
+        rank[file] = Some(kind);
+    }
+
+    rank.map(|kind| kind.unwrap_or(PieceType::Pawn))
+}
@@ -4,7 +4,7 @@
 // Description: Defines the square object and implementation.
 // Author: Seamus Daniello
 // Created: 2025-11-07
-// Last Modified: 2025-11-07
+// Last Modified: 2026-10-18
 // License: MIT
 // =======================================================
 
@@ -15,8 +15,6 @@ use crate::pieces::Piece; // Import from pieces module
 pub struct Square {
     pub piece: Option<Piece>,
     pub gate: Option<GateType>,
-    pub animation_frame: Option<usize>,
-    pub animation_direction: Option<i8>,
 }
 
 impl Square {
@@ -24,8 +22,12 @@ impl Square {
         Square {
             piece: None,
             gate: None,
-            animation_frame: None,
-            animation_direction: None,
         }
     }
 }
+
+impl Default for Square {
+    fn default() -> Self {
+        Self::new()
+    }
+}
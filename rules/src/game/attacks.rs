@@ -0,0 +1,174 @@
+// =======================================================
+// Project: GatedChess
+// File: attacks.rs
+// Description: Attack and defence maps, pins and checks.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Which pieces attack which squares, worked out from the bitboards so
+//! gates, one-way gates and portals count the same way they do for moves.
+//! A piece attacks a square it could capture on if an enemy stood there,
+//! so pawns attack their diagonals and pieces defend friends they attack.
+//!
+//! These are for overlays, bots and analysis. Move generation keeps to the
+//! cheaper `Bitboards::is_attacked`.
+
+use crate::game::bitboard::{Bitboard, SQUARES, opponent, square_bit, square_index, squares};
+use crate::game::{Game, Position};
+use crate::pieces::{Color, PieceType};
+
+/// Every square's attackers, for both sides at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttackMap {
+    attackers: [[Bitboard; SQUARES]; 2],
+}
+
+impl AttackMap {
+    /// Squares of the `color` pieces attacking `square`.
+    pub fn attackers(&self, square: Position, color: Color) -> Bitboard {
+        self.attackers[color as usize][square_index(square)]
+    }
+
+    pub fn attacker_squares(&self, square: Position, color: Color) -> Vec<Position> {
+        squares(self.attackers(square, color)).collect()
+    }
+
+    pub fn count(&self, square: Position, color: Color) -> u32 {
+        self.attackers(square, color).count_ones()
+    }
+
+    /// Every square at least one `color` piece attacks.
+    pub fn attacked(&self, color: Color) -> Bitboard {
+        let mut attacked = 0;
+        for (index, attackers) in self.attackers[color as usize].iter().enumerate() {
+            if *attackers != 0 {
+                attacked |= 1 << index;
+            }
+        }
+        attacked
+    }
+}
+
+/// A piece that can't leave the line between its king and `pinner`
+/// without exposing the king.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pin {
+    pub pinned: Position,
+    pub pinner: Position,
+}
+
+impl Game {
+    pub fn attack_map(&self) -> AttackMap {
+        let bitboards = &self.bitboards;
+        let mut attackers = [[0; SQUARES]; 2];
+        for color in [Color::White, Color::Black] {
+            for kind in PieceType::ALL {
+                for from in squares(bitboards.piece(kind, color)) {
+                    for to in squares(bitboards.attacks(from, kind, color)) {
+                        attackers[color as usize][square_index(to)] |= square_bit(from);
+                    }
+                }
+            }
+        }
+        AttackMap { attackers }
+    }
+
+    /// Squares of the `color` pieces attacking `square`.
+    pub fn attackers_of(&self, square: Position, color: Color) -> Vec<Position> {
+        squares(self.bitboards.attackers(square, color)).collect()
+    }
+
+    /// Squares of the pieces guarding the piece on `square`, if any.
+    pub fn defenders_of(&self, square: Position) -> Vec<Position> {
+        match self.board[square.row][square.col].piece {
+            Some(piece) => self.attackers_of(square, piece.color),
+            None => Vec::new(),
+        }
+    }
+
+    /// Squares of the pieces giving check to `color`'s king.
+    pub fn checkers(&self, color: Color) -> Vec<Position> {
+        match self.bitboards.king_square(color) {
+            Some(king) => self.attackers_of(king, opponent(color)),
+            None => Vec::new(),
+        }
+    }
+
+    /// `color`'s pieces that would expose their king by stepping off the
+    /// square they stand on, each with the piece pinning it. A piece that
+    /// shields its king from more than one attacker is listed once for
+    /// each.
+    pub fn pinned_pieces(&self, color: Color) -> Vec<Pin> {
+        let Some(king) = self.bitboards.king_square(color) else {
+            return Vec::new();
+        };
+        let enemy = opponent(color);
+        let checking = self.bitboards.attackers(king, enemy);
+
+        let mut pins = Vec::new();
+        for pinned in squares(self.bitboards.colors[color as usize] & !square_bit(king)) {
+            let Some(piece) = self.board[pinned.row][pinned.col].piece else {
+                continue;
+            };
+            let mut without = self.bitboards;
+            without.toggle(pinned, piece);
+            for pinner in squares(without.attackers(king, enemy) & !checking) {
+                pins.push(Pin { pinned, pinner });
+            }
+        }
+        pins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attacks_and_defences_count_gates() {
+        let [c1, d1, d4, d7, e1, e2, e8, f3] = ["c1", "d1", "d4", "d7", "e1", "e2", "e8", "f3"]
+            .map(|name| Position::from_algebraic(name).unwrap());
+
+        // The gate on d4 cuts the rook off from d7
+        let game = Game::from_fen("4k3/3p4/8/8/8/8/4P3/3RK3 w - - 0 1 d4:2b").unwrap();
+        let map = game.attack_map();
+        assert_eq!(map.attacker_squares(c1, Color::White), [d1]);
+        assert_eq!(map.count(d7, Color::White), 0);
+        assert_eq!(map.attackers(d4, Color::White), 0);
+
+        // Pawns attack empty diagonals and defend friends
+        assert!(map.attacked(Color::White) & square_bit(f3) != 0);
+        assert_eq!(game.defenders_of(e2), [e1]);
+        assert_eq!(game.defenders_of(d7), [e8]);
+    }
+
+    #[test]
+    fn pins_and_checks_are_listed() {
+        let [a1, a5, d2] = ["a1", "a5", "d2"].map(|name| Position::from_algebraic(name).unwrap());
+
+        let game = Game::from_fen("4k3/8/8/b7/8/8/3N4/r3K3 w - - 0 1").unwrap();
+        assert_eq!(game.checkers(Color::White), [a1]);
+        assert!(game.is_king_in_check(Color::White));
+        assert_eq!(
+            game.pinned_pieces(Color::White),
+            [Pin {
+                pinned: d2,
+                pinner: a5,
+            }]
+        );
+
+        // A pawn only checks from one diagonal step away
+        let pawn = Game::from_fen("8/8/8/8/8/3p4/8/4K2k w - - 0 1").unwrap();
+        assert_eq!(pawn.checkers(Color::White), []);
+        let pawn = Game::from_fen("8/8/8/8/8/8/3p4/4K2k w - - 0 1").unwrap();
+        assert_eq!(pawn.checkers(Color::White), [d2]);
+        assert!(pawn.is_king_in_check(Color::White));
+        // and not while a gate stands under the king
+        let gated = Game::from_fen("8/8/8/8/8/8/3p4/4K2k w - - 0 1 e1:2w").unwrap();
+        assert_eq!(gated.checkers(Color::White), []);
+        assert!(!gated.is_king_in_check(Color::White));
+    }
+}
@@ -0,0 +1,705 @@
+// =======================================================
+// Project: GatedChess
+// File: bitboard.rs
+// Description: Bitboard view of the board and attack tables.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! One bit per square, with bit `row * 8 + col` standing for `Position { row,
+//! col }`, so a1 is bit 0 and h8 is bit 63.
+//!
+//! `Game` keeps a `Bitboards` in step with its square array. Every write to a
+//! square's piece or gate goes through `set_piece` and `set_gate`, which also
+//! keep the Zobrist hash current. The square array stays the copy the
+//! frontend draws from.
+
+use crate::board::Square;
+use crate::config::BOARD_SIZE;
+use crate::game::{Game, Position, zobrist};
+use crate::gates::{Direction, GateType};
+use crate::pieces::{Color, Piece, PieceType};
+
+pub type Bitboard = u64;
+
+pub(crate) const SQUARES: usize = BOARD_SIZE * BOARD_SIZE;
+const FILE_A: Bitboard = 0x0101_0101_0101_0101;
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+const KING_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rook directions first, then bishop directions.
+const DIRECTIONS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+static KNIGHT_ATTACKS: [Bitboard; SQUARES] = step_table(&KNIGHT_OFFSETS);
+static KING_ATTACKS: [Bitboard; SQUARES] = step_table(&KING_OFFSETS);
+/// Squares a pawn of each colour attacks, indexed by `Color as usize`.
+static PAWN_ATTACKS: [[Bitboard; SQUARES]; 2] =
+    [step_table(&[(1, -1), (1, 1)]), step_table(&[(-1, -1), (-1, 1)])];
+/// Every square from a square to the edge in each of `DIRECTIONS`.
+static RAYS: [[Bitboard; SQUARES]; 8] = ray_table();
+/// Squares strictly between two squares on a shared line, or empty.
+static BETWEEN: [[Bitboard; SQUARES]; SQUARES] = between_table();
+
+const fn offset_index(index: usize, dr: i32, dc: i32) -> Option<usize> {
+    let row = (index / BOARD_SIZE) as i32 + dr;
+    let col = (index % BOARD_SIZE) as i32 + dc;
+    if row < 0 || row >= BOARD_SIZE as i32 || col < 0 || col >= BOARD_SIZE as i32 {
+        None
+    } else {
+        Some(row as usize * BOARD_SIZE + col as usize)
+    }
+}
+
+const fn step_table(offsets: &[(i32, i32)]) -> [Bitboard; SQUARES] {
+    let mut table = [0; SQUARES];
+    let mut index = 0;
+    while index < SQUARES {
+        let mut i = 0;
+        while i < offsets.len() {
+            if let Some(target) = offset_index(index, offsets[i].0, offsets[i].1) {
+                table[index] |= 1 << target;
+            }
+            i += 1;
+        }
+        index += 1;
+    }
+    table
+}
+
+const fn ray_table() -> [[Bitboard; SQUARES]; 8] {
+    let mut table = [[0; SQUARES]; 8];
+    let mut dir = 0;
+    while dir < DIRECTIONS.len() {
+        let (dr, dc) = DIRECTIONS[dir];
+        let mut index = 0;
+        while index < SQUARES {
+            let mut current = index;
+            while let Some(next) = offset_index(current, dr, dc) {
+                table[dir][index] |= 1 << next;
+                current = next;
+            }
+            index += 1;
+        }
+        dir += 1;
+    }
+    table
+}
+
+const fn between_table() -> [[Bitboard; SQUARES]; SQUARES] {
+    let mut table = [[0; SQUARES]; SQUARES];
+    let mut dir = 0;
+    while dir < DIRECTIONS.len() {
+        let (dr, dc) = DIRECTIONS[dir];
+        let mut from = 0;
+        while from < SQUARES {
+            let mut path = 0;
+            let mut current = from;
+            while let Some(next) = offset_index(current, dr, dc) {
+                table[from][next] = path;
+                path |= 1 << next;
+                current = next;
+            }
+            from += 1;
+        }
+        dir += 1;
+    }
+    table
+}
+
+pub fn square_index(pos: Position) -> usize {
+    pos.row * BOARD_SIZE + pos.col
+}
+
+pub fn square_bit(pos: Position) -> Bitboard {
+    1 << square_index(pos)
+}
+
+pub fn index_position(index: usize) -> Position {
+    Position {
+        row: index / BOARD_SIZE,
+        col: index % BOARD_SIZE,
+    }
+}
+
+/// The squares set in `bb`, lowest first.
+pub fn squares(mut bb: Bitboard) -> impl Iterator<Item = Position> {
+    std::iter::from_fn(move || {
+        if bb == 0 {
+            return None;
+        }
+        let index = bb.trailing_zeros() as usize;
+        bb &= bb - 1;
+        Some(index_position(index))
+    })
+}
+
+pub fn between(from: Position, to: Position) -> Bitboard {
+    BETWEEN[square_index(from)][square_index(to)]
+}
+
+/// Squares a slider on `index` reaches along `DIRECTIONS[dir]`, up to and
+/// including the first blocker.
+fn ray_attacks(index: usize, dir: usize, blockers: Bitboard) -> Bitboard {
+    let ray = RAYS[dir][index];
+    let hits = ray & blockers;
+    if hits == 0 {
+        return ray;
+    }
+    let (dr, dc) = DIRECTIONS[dir];
+    let first = if dr * BOARD_SIZE as i32 + dc > 0 {
+        hits.trailing_zeros() as usize
+    } else {
+        63 - hits.leading_zeros() as usize
+    };
+    ray & !RAYS[dir][first]
+}
+
+fn slider_attacks(index: usize, dirs: std::ops::Range<usize>, blockers: Bitboard) -> Bitboard {
+    dirs.map(|dir| ray_attacks(index, dir, blockers))
+        .fold(0, |acc, bb| acc | bb)
+}
+
+fn ranks_below(row: usize) -> Bitboard {
+    (1 << (row * BOARD_SIZE)) - 1
+}
+
+fn ranks_above(row: usize) -> Bitboard {
+    Bitboard::MAX
+        .checked_shl(((row + 1) * BOARD_SIZE) as u32)
+        .unwrap_or(0)
+}
+
+fn files_left_of(col: usize) -> Bitboard {
+    ((1 << col) - 1) * FILE_A
+}
+
+fn files_right_of(col: usize) -> Bitboard {
+    (0xFF & !((1 << (col + 1)) - 1)) * FILE_A
+}
+
+pub(crate) fn opponent(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bitboards {
+    /// Indexed by `Color as usize`, then `PieceType as usize`.
+    pub pieces: [[Bitboard; 6]; 2],
+    pub colors: [Bitboard; 2],
+    /// Every gate, whatever its kind.
+    pub gates: Bitboard,
+    /// `gates` split by the side that made them.
+    pub owned_gates: [Bitboard; 2],
+    /// One-way gates, indexed by the `Direction as usize` they let pieces
+    /// through in.
+    pub one_way: [Bitboard; 4],
+    pub portals: Bitboard,
+    /// The other end of each portal in `portals`, as a square index.
+    pub portal_exits: [u8; SQUARES],
+    /// Gates that come down when the current ply ends.
+    pub expiring: Bitboard,
+    /// Copied from the game's `GateRules`. When false, gates only stop the
+    /// opponent of their owner.
+    pub blocks_owner: bool,
+}
+
+impl Default for Bitboards {
+    fn default() -> Self {
+        Bitboards {
+            pieces: [[0; 6]; 2],
+            colors: [0; 2],
+            gates: 0,
+            owned_gates: [0; 2],
+            one_way: [0; 4],
+            portals: 0,
+            portal_exits: [0; SQUARES],
+            expiring: 0,
+            blocks_owner: false,
+        }
+    }
+}
+
+impl Bitboards {
+    pub fn from_board(board: &[[Square; BOARD_SIZE]; BOARD_SIZE], blocks_owner: bool) -> Bitboards {
+        let mut bitboards = Bitboards {
+            blocks_owner,
+            ..Bitboards::default()
+        };
+        for (row, squares) in board.iter().enumerate() {
+            for (col, square) in squares.iter().enumerate() {
+                let pos = Position { row, col };
+                if let Some(piece) = square.piece {
+                    bitboards.toggle(pos, piece);
+                }
+                if let Some(gate) = square.gate {
+                    bitboards.add_gate(pos, gate);
+                }
+            }
+        }
+        bitboards
+    }
+
+    /// Adds `piece` on `pos`, or takes it off if it is already there.
+    pub fn toggle(&mut self, pos: Position, piece: Piece) {
+        let bit = square_bit(pos);
+        self.pieces[piece.color as usize][piece.kind as usize] ^= bit;
+        self.colors[piece.color as usize] ^= bit;
+    }
+
+    fn add_gate(&mut self, pos: Position, gate: GateType) {
+        let bit = square_bit(pos);
+        self.gates |= bit;
+        self.owned_gates[gate.owner() as usize] |= bit;
+        if gate.duration() == Some(1) {
+            self.expiring |= bit;
+        }
+        match gate {
+            GateType::OneWay { toward, .. } => self.one_way[toward as usize] |= bit,
+            GateType::Portal { exit, .. } => {
+                self.portals |= bit;
+                self.portal_exits[square_index(pos)] = square_index(exit) as u8;
+            }
+            GateType::Standard { .. } | GateType::Wall { .. } => {}
+        }
+    }
+
+    fn remove_gate(&mut self, pos: Position) {
+        let keep = !square_bit(pos);
+        *self = self.keep_gates(keep);
+        self.portal_exits[square_index(pos)] = 0;
+    }
+
+    /// The same position with only the gates in `keep` left standing.
+    pub fn keep_gates(&self, keep: Bitboard) -> Bitboards {
+        Bitboards {
+            gates: self.gates & keep,
+            owned_gates: self.owned_gates.map(|bb| bb & keep),
+            one_way: self.one_way.map(|bb| bb & keep),
+            portals: self.portals & keep,
+            expiring: self.expiring & keep,
+            ..*self
+        }
+    }
+
+    /// Gates that `color`'s pieces can't enter or pass, whichever way they
+    /// move. Nothing may stop on a portal, so portals are always included.
+    pub fn gates_against(&self, color: Color) -> Bitboard {
+        let owned = if self.blocks_owner {
+            self.gates
+        } else {
+            self.owned_gates[opponent(color) as usize]
+        };
+        owned | self.portals
+    }
+
+    /// Gates that stop a `color` piece moving from `from`. One-way gates
+    /// only count when the move would cross them the wrong way, which
+    /// depends on which side of `from` they stand.
+    pub fn gates_blocking(&self, color: Color, from: Position) -> Bitboard {
+        let against = self.gates_against(color);
+        let one_way = self.one_way.iter().fold(0, |acc, bb| acc | bb);
+        if one_way == 0 {
+            return against;
+        }
+        let wrong_way = self.one_way[Direction::North as usize] & ranks_below(from.row)
+            | self.one_way[Direction::South as usize] & ranks_above(from.row)
+            | self.one_way[Direction::East as usize] & files_left_of(from.col)
+            | self.one_way[Direction::West as usize] & files_right_of(from.col);
+        against & (!one_way | wrong_way)
+    }
+
+    pub fn piece(&self, kind: PieceType, color: Color) -> Bitboard {
+        self.pieces[color as usize][kind as usize]
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<Position> {
+        let kings = self.piece(PieceType::King, color);
+        (kings != 0).then(|| index_position(kings.trailing_zeros() as usize))
+    }
+
+    /// Squares a piece on `pos` moves to or captures on, before pins and
+    /// castling. Gates that block the piece can never be entered, and
+    /// sliders stop in front of them.
+    pub fn targets(&self, pos: Position, piece: Piece, en_passant: Option<Position>) -> Bitboard {
+        if piece.kind == PieceType::Pawn {
+            return self.pawn_targets(pos, piece.color, en_passant);
+        }
+        self.attacks(pos, piece.kind, piece.color) & !self.colors[piece.color as usize]
+    }
+
+    /// Squares a `color` piece of `kind` on `pos` attacks, whoever stands
+    /// on them. Pawns attack their diagonals, and sliders reach through
+    /// portals.
+    pub fn attacks(&self, pos: Position, kind: PieceType, color: Color) -> Bitboard {
+        let index = square_index(pos);
+        let stop = self.gates_blocking(color, pos);
+        match kind {
+            PieceType::Knight => KNIGHT_ATTACKS[index] & !stop,
+            PieceType::King => KING_ATTACKS[index] & !stop,
+            PieceType::Pawn => PAWN_ATTACKS[color as usize][index] & !stop,
+            PieceType::Rook => self.slider_reach(pos, 0..4, color),
+            PieceType::Bishop => self.slider_reach(pos, 4..8, color),
+            PieceType::Queen => self.slider_reach(pos, 0..8, color),
+        }
+    }
+
+    fn slider_reach(&self, pos: Position, dirs: std::ops::Range<usize>, color: Color) -> Bitboard {
+        dirs.map(|dir| self.ray_reach(pos, dir, color, true))
+            .fold(0, |acc, bb| acc | bb)
+    }
+
+    /// Squares a slider on `pos` reaches along `DIRECTIONS[dir]`. A ray that
+    /// runs into an empty portal carries on from its exit, but only once, so
+    /// two portals can't send a piece round in circles.
+    fn ray_reach(&self, pos: Position, dir: usize, color: Color, through_portal: bool) -> Bitboard {
+        let stop = self.gates_blocking(color, pos);
+        let occupied = self.occupied();
+        let hits = ray_attacks(square_index(pos), dir, occupied | stop);
+        let mut reach = hits & !stop;
+
+        let portal = hits & self.portals & !occupied;
+        if through_portal && portal != 0 {
+            let exit = self.portal_exits[portal.trailing_zeros() as usize] as usize;
+            if occupied & (1 << exit) == 0 {
+                reach |= self.ray_reach(index_position(exit), dir, color, false);
+            }
+        }
+        reach
+    }
+
+    fn pawn_targets(&self, pos: Position, color: Color, en_passant: Option<Position>) -> Bitboard {
+        let index = square_index(pos);
+        let blockers = self.occupied() | self.gates_blocking(color, pos);
+        let (step, start_row): (i32, usize) = match color {
+            Color::White => (1, crate::config::WHITE_PAWN_ROW),
+            Color::Black => (-1, crate::config::BLACK_PAWN_ROW),
+        };
+
+        let mut targets = 0;
+        if let Some(forward) = offset_index(index, step, 0)
+            && blockers & (1 << forward) == 0
+        {
+            targets |= 1 << forward;
+            if pos.row == start_row
+                && let Some(double) = offset_index(forward, step, 0)
+                && blockers & (1 << double) == 0
+            {
+                targets |= 1 << double;
+            }
+        }
+
+        // The en passant square only counts for the side whose opponent just
+        // pushed past it, so the pushed pawn must stand one step beyond it
+        let mut capturable = self.colors[opponent(color) as usize];
+        if let Some(ep) = en_passant
+            && let Some(pushed) = offset_index(square_index(ep), -step, 0)
+            && self.piece(PieceType::Pawn, opponent(color)) & (1 << pushed) != 0
+        {
+            capturable |= square_bit(ep);
+        }
+        // A gate blocks captures just like pushes
+        targets | (self.attacks(pos, PieceType::Pawn, color) & capturable)
+    }
+
+    /// Squares of the `by` pieces that attack `target`, by the same rules as
+    /// `attacks`. Pieces standing on `target` are never counted.
+    pub fn attackers(&self, target: Position, by: Color) -> Bitboard {
+        let bit = square_bit(target);
+        let mut found = 0;
+        for kind in PieceType::ALL {
+            for from in squares(self.piece(kind, by) & !bit) {
+                if self.attacks(from, kind, by) & bit != 0 {
+                    found |= square_bit(from);
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns true if any piece of `by` attacks `target`. Pawns attack their
+    /// diagonals whether or not anything stands there. A square behind a gate
+    /// that blocks `by` can't be taken, so it is never attacked.
+    pub fn is_attacked(&self, target: Position, by: Color) -> bool {
+        // One-way gates and portals make attacks depend on where they come
+        // from, so look from each attacker instead of back from the target
+        if self.portals != 0 || self.one_way.iter().any(|bb| *bb != 0) {
+            let bit = square_bit(target);
+            return PieceType::ALL.into_iter().any(|kind| {
+                squares(self.piece(kind, by)).any(|from| self.attacks(from, kind, by) & bit != 0)
+            });
+        }
+
+        let index = square_index(target);
+        let pieces = &self.pieces[by as usize];
+        let gates = self.gates_against(by);
+        if gates & square_bit(target) != 0 {
+            return false;
+        }
+        let blockers = self.occupied() | gates;
+
+        let straight = pieces[PieceType::Rook as usize] | pieces[PieceType::Queen as usize];
+        let diagonal = pieces[PieceType::Bishop as usize] | pieces[PieceType::Queen as usize];
+
+        KNIGHT_ATTACKS[index] & pieces[PieceType::Knight as usize] != 0
+            || KING_ATTACKS[index] & pieces[PieceType::King as usize] != 0
+            || PAWN_ATTACKS[opponent(by) as usize][index] & pieces[PieceType::Pawn as usize] != 0
+            || slider_attacks(index, 0..4, blockers) & straight != 0
+            || slider_attacks(index, 4..8, blockers) & diagonal != 0
+    }
+}
+
+impl Game {
+    /// Puts `piece` on `pos`, replacing whatever stood there.
+    pub(crate) fn set_piece(&mut self, pos: Position, piece: Option<Piece>) {
+        if let Some(old) = self.board[pos.row][pos.col].piece {
+            self.bitboards.toggle(pos, old);
+            self.hash ^= zobrist::piece_key(pos, old);
+        }
+        if let Some(new) = piece {
+            self.bitboards.toggle(pos, new);
+            self.hash ^= zobrist::piece_key(pos, new);
+        }
+        self.board[pos.row][pos.col].piece = piece;
+    }
+
+    pub(crate) fn set_gate(&mut self, pos: Position, gate: Option<GateType>) {
+        if let Some(old) = self.board[pos.row][pos.col].gate {
+            self.bitboards.remove_gate(pos);
+            self.hash ^= zobrist::gate_key(pos, old);
+        }
+        if let Some(new) = gate {
+            self.bitboards.add_gate(pos, new);
+            self.hash ^= zobrist::gate_key(pos, new);
+        }
+        self.board[pos.row][pos.col].gate = gate;
+    }
+
+    /// Returns true if moving the piece on `from` to `to` leaves `color`'s
+    /// king attacked. Works on a copy of the bitboards instead of playing the
+    /// move, counting the gates the move itself would leave behind and
+    /// dropping those that come down as the ply ends.
+    pub(crate) fn move_exposes_king(&self, from: Position, to: Position, color: Color) -> bool {
+        let Some(piece) = self.board[from.row][from.col].piece else {
+            return false;
+        };
+        let mut after = self.bitboards.keep_gates(!self.bitboards.expiring);
+
+        let castling = self.castling_side(from, to);
+        let landing = match castling {
+            Some(side) => Position {
+                row: from.row,
+                col: side.king_target_col(),
+            },
+            None => to,
+        };
+
+        let rules = &self.gate_rules;
+        let mut new_gates = 0;
+        after.toggle(from, piece);
+        if let Some(side) = castling {
+            // Both come off before either goes back, as in make_move
+            let rook_from = Position {
+                row: from.row,
+                col: self.castling_files.rook(side),
+            };
+            let rook_to = Position {
+                row: from.row,
+                col: side.rook_target_col(),
+            };
+            let rook = self.board[rook_from.row][rook_from.col].piece;
+            if let Some(rook) = rook {
+                after.toggle(rook_from, rook);
+                after.toggle(rook_to, rook);
+            }
+            after.toggle(landing, piece);
+            if rook.is_some() && rook_from != rook_to {
+                new_gates |= rules.gate_squares(PieceType::Rook, rook_from, rook_to, after.occupied());
+            }
+        } else {
+            let captured_pos = self.en_passant_capture_square(from, to).unwrap_or(to);
+            if let Some(captured) = self.board[captured_pos.row][captured_pos.col].piece {
+                after.toggle(captured_pos, captured);
+            }
+            after.toggle(to, piece);
+        }
+        if landing != from {
+            new_gates |= rules.gate_squares(piece.kind, from, landing, after.occupied());
+        }
+        // One-ply gates are gone again before the opponent moves
+        if rules.duration == 1 {
+            new_gates = 0;
+        }
+        let gate = GateType::Standard {
+            duration: rules.duration,
+            owner: color,
+        };
+        for pos in squares(new_gates) {
+            after.add_gate(pos, gate);
+        }
+
+        let king = if piece.kind == PieceType::King {
+            Some(landing)
+        } else {
+            after.king_square(color)
+        };
+        king.is_some_and(|king| after.is_attacked(king, opponent(color)))
+    }
+
+    /// Returns true if `color`'s king is attacked once they place a gate on
+    /// `square` and the ply ends. A gate only ever blocks, so this is only
+    /// true when the gate fails to cover a check.
+    pub(crate) fn placement_exposes_king(&self, square: Position, color: Color) -> bool {
+        let mut after = self.bitboards.keep_gates(!self.bitboards.expiring);
+        if self.gate_rules.duration > 1 {
+            after.add_gate(
+                square,
+                GateType::Standard {
+                    duration: self.gate_rules.duration,
+                    owner: color,
+                },
+            );
+        }
+        after
+            .king_square(color)
+            .is_some_and(|king| after.is_attacked(king, opponent(color)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bitboards, squares};
+    use crate::game::{Game, Position};
+    use crate::gates::GateRules;
+    use crate::parser::algebraic::parse_move;
+    use crate::pieces::{Color, Piece, PieceType};
+
+    #[test]
+    fn owned_gates_only_block_the_opponent() {
+        let board = "4k3/8/8/8/r3K3/8/8/8 w - - 0 1";
+        for (owner, in_check) in [("w", false), ("b", true)] {
+            let mut game = Game::from_fen(&format!("{} c4:2{}", board, owner)).unwrap();
+            assert!(!game.is_king_in_check(Color::White), "{}", owner);
+            game.set_gate_rules(GateRules {
+                blocks_owner: false,
+                ..GateRules::STANDARD
+            });
+            assert_eq!(game.is_king_in_check(Color::White), in_check, "{}", owner);
+        }
+    }
+
+    fn rook_targets(fen: &str) -> Vec<String> {
+        let game = Game::from_fen(fen).unwrap();
+        let rook = Piece::new(PieceType::Rook, Color::White);
+        let a1 = Position { row: 0, col: 0 };
+        squares(game.bitboards.targets(a1, rook, None))
+            .map(|pos| pos.to_algebraic())
+            .collect()
+    }
+
+    #[test]
+    fn one_way_gates_only_block_one_way() {
+        let board = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1";
+        assert!(rook_targets(&format!("{} a4:2b^", board)).contains(&"a8".to_string()));
+        assert!(rook_targets(&format!("{} a4:2b>", board)).contains(&"a8".to_string()));
+        let blocked = rook_targets(&format!("{} a4:2bv", board));
+        assert!(blocked.contains(&"a3".to_string()));
+        assert!(!blocked.contains(&"a4".to_string()));
+    }
+
+    #[test]
+    fn portals_carry_sliders_through() {
+        let portals = "a4:2b@h5,h5:2b@a4";
+        let targets = rook_targets(&format!("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 {}", portals));
+        for square in ["a3", "h6", "h8"] {
+            assert!(targets.contains(&square.to_string()), "{}", square);
+        }
+        for square in ["a4", "a5", "h5"] {
+            assert!(!targets.contains(&square.to_string()), "{}", square);
+        }
+
+        let game = Game::from_fen(&format!("8/7k/8/8/8/8/8/R3K3 b - - 0 1 {}", portals)).unwrap();
+        assert!(game.is_king_in_check(Color::Black));
+    }
+
+    #[test]
+    fn walls_outlast_everything() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1 h1:2b@h8,a4:#w,b4:1w,c4:3b<,h8:2b@h1";
+        let mut game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.to_fen(), fen);
+        for text in ["e1d1", "e8d8", "d1e1", "d8e8", "e1d1"] {
+            game.make_move(parse_move(text).unwrap()).unwrap();
+        }
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/R2K4 b - - 5 3 a4:#w");
+    }
+
+    #[test]
+    fn en_passant_only_takes_a_pushed_enemy_pawn() {
+        let mut game = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        let d4 = Position::from_algebraic("d4").unwrap();
+        assert!(game.get_legal_moves(d4).contains(&Position::from_algebraic("e3").unwrap()));
+        game.make_move(parse_move("d4e3").unwrap()).unwrap();
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/4p3/8/4K3 w - - 0 2");
+
+        // A stale en passant square behind the mover's own pawn is no capture
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/3PP3/4K3 w - - 0 1").unwrap();
+        game.en_passant = Position::from_algebraic("e3");
+        let d2 = Position::from_algebraic("d2").unwrap();
+        assert!(!game.get_legal_moves(d2).contains(&Position::from_algebraic("e3").unwrap()));
+        assert!(game.make_move(parse_move("d2e3").unwrap()).is_err());
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/3PP3/4K3 w - e3 0 1");
+    }
+
+    #[test]
+    fn stays_in_step_with_board() {
+        // Castling, a bishop gate, en passant and promotion all write squares
+        let mut game = Game::from_fen("r3k2r/1P6/8/8/3pP3/8/8/R1B1K2R b KQkq e3 0 1").unwrap();
+        for text in ["d4e3", "e1g1", "e3e2", "c1a3", "a8a4", "b7b8q"] {
+            let mv = crate::parser::algebraic::parse_move(text).unwrap();
+            game.make_move(mv).unwrap();
+            let rebuilt = Bitboards::from_board(&game.board, game.gate_rules.blocks_owner);
+            assert_eq!(game.bitboards, rebuilt, "{}", text);
+        }
+        while game.unmake_move().is_some() {
+            let rebuilt = Bitboards::from_board(&game.board, game.gate_rules.blocks_owner);
+            assert_eq!(game.bitboards, rebuilt);
+        }
+    }
+}
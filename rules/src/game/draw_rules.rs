@@ -0,0 +1,111 @@
+// =======================================================
+// Project: GatedChess
+// File: draw_rules.rs
+// Description: Fifty-move, repetition and material draw rules.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::config::BOARD_SIZE;
+use crate::game::Game;
+use crate::gates::GateType;
+use crate::pieces::PieceType;
+
+/// Halfmoves without a pawn move or capture before the game is drawn.
+pub const FIFTY_MOVE_LIMIT: u32 = 100;
+
+impl Game {
+    /// Number of times the current position has occurred, counting this one.
+    pub fn repetition_count(&self) -> usize {
+        let key = self.position_key();
+        self.position_history
+            .iter()
+            .filter(|&&seen| seen == key)
+            .count()
+    }
+
+    /// True if neither side has enough material left to mate: bare kings, a
+    /// single minor piece, or only bishops that all stand on one colour.
+    /// A minor piece that raises gates under the current rules can help box
+    /// a king in, so it always counts, and so does any wall on the board or
+    /// gate still to be placed.
+    pub fn is_insufficient_material(&self) -> bool {
+        if self.gate_stock != [0; 2] {
+            return false;
+        }
+
+        let mut minors = 0;
+        let mut knights = 0;
+        let mut bishop_square_colors = [false; 2];
+
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                if matches!(self.board[row][col].gate, Some(GateType::Wall { .. })) {
+                    return false;
+                }
+                let Some(piece) = self.board[row][col].piece else {
+                    continue;
+                };
+                match piece.kind {
+                    PieceType::King => {}
+                    PieceType::Knight | PieceType::Bishop if self.gate_rules.spawns(piece.kind) => {
+                        return false;
+                    }
+                    PieceType::Knight => {
+                        minors += 1;
+                        knights += 1;
+                    }
+                    PieceType::Bishop => {
+                        minors += 1;
+                        bishop_square_colors[(row + col) % 2] = true;
+                    }
+                    PieceType::Pawn | PieceType::Rook | PieceType::Queen => return false,
+                }
+            }
+        }
+
+        minors <= 1 || (knights == 0 && !(bishop_square_colors[0] && bishop_square_colors[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gates::GateRules;
+
+    #[test]
+    fn walls_count_as_material() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1 a4:2w").unwrap();
+        assert!(game.is_insufficient_material());
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1 a4:#w").unwrap();
+        assert!(!game.is_insufficient_material());
+    }
+
+    #[test]
+    fn gates_left_to_place_count_as_material() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1 - 0/1").unwrap();
+        assert!(!game.is_insufficient_material());
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert!(game.is_insufficient_material());
+    }
+
+    #[test]
+    fn minor_pieces_that_raise_gates_count_as_material() {
+        let fen = "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1";
+        let game = Game::from_fen_with_rules(fen, GateRules::NONE).unwrap();
+        assert!(game.is_insufficient_material());
+        let game = Game::from_fen_with_rules(fen, GateRules::STANDARD).unwrap();
+        assert!(!game.is_insufficient_material());
+
+        let knights = GateRules {
+            spawners: [false, false, true, false, false, false],
+            ..GateRules::STANDARD
+        };
+        let game = Game::from_fen_with_rules("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1", knights).unwrap();
+        assert!(!game.is_insufficient_material());
+        let game = Game::from_fen_with_rules(fen, knights).unwrap();
+        assert!(game.is_insufficient_material());
+    }
+}
@@ -0,0 +1,203 @@
+// =======================================================
+// Project: GatedChess
+// File: events.rs
+// Description: Events a game reports as moves are played.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! `Game::make_move`, `unmake_move` and `redo_move` queue up what happened
+//! on the board, in the order it happened, for the frontend, network layer
+//! or anything else to react to. Consumers take them with `drain_events`
+//! after each move. The unchecked make/unmake used by search and perft
+//! queue nothing.
+
+use crate::game::bitboard::{between, square_bit, squares};
+use crate::game::{Game, GameResult, Move, Position};
+use crate::pieces::{Color, Piece, PieceType};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    PieceMoved {
+        piece: Piece,
+        from: Position,
+        to: Position,
+    },
+    PieceCaptured {
+        piece: Piece,
+        square: Position,
+    },
+    PiecePromoted {
+        piece: Piece,
+        square: Position,
+    },
+    /// A gate went up, by a piece moving or by being placed.
+    GateSpawned {
+        square: Position,
+        owner: Color,
+    },
+    /// A gate's time ran out as the ply ended.
+    GateExpired {
+        square: Position,
+    },
+    /// A move was turned down because of the gate on `gate`, and would have
+    /// been possible without it.
+    MoveBlockedByGate {
+        from: Position,
+        to: Position,
+        gate: Position,
+    },
+    /// `color`'s king on `king` is attacked.
+    Check {
+        color: Color,
+        king: Position,
+    },
+    GameOver(GameResult),
+    /// `unmake_move` took `mv` back. Gates and pieces are as they were
+    /// before it, without events of their own.
+    MoveTakenBack(Move),
+}
+
+impl Game {
+    /// Takes every event queued since the last call, oldest first.
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Queues the events for a move `make_move` has just played.
+    /// The piece FSMs follow them as well.
+    pub(crate) fn record_move_events(&mut self, played: &Move) {
+        let first = self.events.len();
+        if let Some(piece) = played.piece {
+            self.events.push(GameEvent::PieceMoved {
+                piece,
+                from: played.from,
+                to: played.landing(),
+            });
+        }
+        if let Some(piece) = played.captured {
+            let square = if played.en_passant {
+                Position {
+                    row: played.from.row,
+                    col: played.to.col,
+                }
+            } else {
+                played.to
+            };
+            self.events.push(GameEvent::PieceCaptured { piece, square });
+        }
+        if played.promotion.is_some()
+            && let Some(piece) = self.board[played.to.row][played.to.col].piece
+        {
+            self.events.push(GameEvent::PiecePromoted {
+                piece,
+                square: played.to,
+            });
+        }
+
+        // The side that moved raised every new gate
+        let owner = match self.current_turn {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        for &square in &played.gates_created {
+            self.events.push(GameEvent::GateSpawned { square, owner });
+        }
+        for &square in &played.gates_expired {
+            self.events.push(GameEvent::GateExpired { square });
+        }
+
+        let color = self.current_turn;
+        if let Some(king) = self.bitboards.king_square(color)
+            && self.is_king_in_check(color)
+        {
+            self.events.push(GameEvent::Check { color, king });
+        }
+        if self.result != GameResult::InProgress {
+            self.events.push(GameEvent::GameOver(self.result.clone()));
+        }
+        self.state_manager.handle_move(&self.events[first..]);
+    }
+
+    /// Queues `MoveBlockedByGate` if the piece on `from` could reach `to`
+    /// were it not for a gate. The gate nearest `from` is named.
+    pub(crate) fn record_blocked_move(&mut self, from: Position, to: Position) {
+        let Some(piece) = self.board[from.row][from.col].piece else {
+            return;
+        };
+        let open = self.bitboards.keep_gates(0);
+        if open.targets(from, piece, self.en_passant) & square_bit(to) == 0 {
+            return;
+        }
+
+        // Knights and kings don't pass over squares, only onto them
+        let path = match piece.kind {
+            PieceType::Knight | PieceType::King => square_bit(to),
+            _ => between(from, to) | square_bit(to),
+        };
+        let gate = squares(path & self.bitboards.gates_blocking(piece.color, from))
+            .min_by_key(|pos| pos.row.abs_diff(from.row).max(pos.col.abs_diff(from.col)));
+        if let Some(gate) = gate {
+            self.events.push(GameEvent::MoveBlockedByGate { from, to, gate });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::algebraic::parse_move;
+
+    #[test]
+    fn gates_report_going_up_and_coming_down() {
+        let mut game = Game::from_fen("4k3/p7/8/8/8/8/P7/2B1K3 w - - 0 1").unwrap();
+        game.make_move(parse_move("c1f4").unwrap()).unwrap();
+        let events = game.drain_events();
+        assert_eq!(
+            events[0],
+            GameEvent::PieceMoved {
+                piece: game.board[3][5].piece.unwrap(),
+                from: Position::from_algebraic("c1").unwrap(),
+                to: Position::from_algebraic("f4").unwrap(),
+            }
+        );
+        let spawned = events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::GateSpawned { owner: Color::White, .. }))
+            .count();
+        assert_eq!(spawned, 2);
+
+        game.make_move(parse_move("e8d8").unwrap()).unwrap();
+        let expired = game
+            .drain_events()
+            .into_iter()
+            .filter(|event| matches!(event, GameEvent::GateExpired { .. }))
+            .count();
+        assert_eq!(expired, 2);
+        assert!(game.drain_events().is_empty());
+    }
+
+    #[test]
+    fn blocked_moves_and_checks_are_reported() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 a3:2b,a6:2b").unwrap();
+        assert!(game.make_move(parse_move("a1a8").unwrap()).is_err());
+        assert_eq!(
+            game.drain_events(),
+            [GameEvent::MoveBlockedByGate {
+                from: Position::from_algebraic("a1").unwrap(),
+                to: Position::from_algebraic("a8").unwrap(),
+                gate: Position::from_algebraic("a3").unwrap(),
+            }]
+        );
+
+        game.make_move(parse_move("a1a2").unwrap()).unwrap();
+        game.make_move(parse_move("e8d8").unwrap()).unwrap();
+        game.make_move(parse_move("a2d2").unwrap()).unwrap();
+        assert!(game.drain_events().contains(&GameEvent::Check {
+            color: Color::Black,
+            king: Position::from_algebraic("d8").unwrap(),
+        }));
+    }
+}
@@ -0,0 +1,541 @@
+// =======================================================
+// Project: GatedChess
+// File: fen.rs
+// Description: Reads and writes positions as FEN with gates.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Positions use standard FEN with an optional seventh field listing the
+//! gates on the board as `square:duration` pairs, each followed by `w` or `b`
+//! for the side that raised it, for example
+//!
+//! `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 c1:2b,d2:1b`
+//!
+//! Other kinds of gate add to that: `d4:#w` is a wall, which has no
+//! duration, `d4:2w^` a one-way gate letting pieces through northwards
+//! (`>`, `v` and `<` for the other ways), and `d4:2w@e6` one end of a
+//! portal whose other end is on e6. Both ends of a portal must be listed.
+//!
+//! A gate without an owner belongs to the side that just moved. The field is
+//! left out when there are no gates, so gate-free positions read the same as
+//! in any other chess tool.
+//!
+//! Castling rights are `KQkq` when kings and rooks start where they do in
+//! standard chess. Other layouts, as in Chess960, name the file of each
+//! castling rook instead, such as `HAha`. `K` and `Q` are read there too,
+//! as the outermost rook on that side of the king.
+//!
+//! Games where players place gates add an eighth field with the gates White
+//! and Black have left, such as `3/2`. The gates field is then written as
+//! `-` if there are none on the board.
+
+use crate::board::Square;
+use crate::config::{BLACK_PAWN_ROW, BOARD_SIZE, WHITE_PAWN_ROW};
+use crate::game::moves::castling::{CastlingFiles, CastlingRights, CastlingSide, back_rank};
+use crate::game::{Game, Position};
+use crate::gates::{Direction, GateRules, GateType};
+use crate::pieces::{Color, Piece, PieceType};
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenError {
+    WrongFieldCount,
+    BadBoard,
+    /// Each side needs exactly one king.
+    BadKings,
+    BadTurn,
+    BadCastling,
+    BadEnPassant,
+    BadClock,
+    BadGates,
+    BadGateStock,
+}
+
+impl Game {
+    /// Reads a FEN into a game under the standard gate rules.
+    pub fn from_fen(fen: &str) -> Result<Game, FenError> {
+        Game::from_fen_with_rules(fen, GateRules::default())
+    }
+
+    /// Reads a FEN into a game played under `gate_rules`. A FEN says nothing
+    /// of the rules, so games that don't use the standard ones must be
+    /// loaded with theirs. The stock of gates to place still comes from the
+    /// FEN.
+    pub fn from_fen_with_rules(fen: &str, gate_rules: GateRules) -> Result<Game, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if !(6..=8).contains(&fields.len()) {
+            return Err(FenError::WrongFieldCount);
+        }
+
+        let mut board = parse_board(fields[0])?;
+
+        let current_turn = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(FenError::BadTurn),
+        };
+
+        // Gates may stand under pieces, since some rules raise them on the
+        // square a piece lands on
+        if let Some(gates) = fields.get(6) {
+            let just_moved = match current_turn {
+                Color::White => Color::Black,
+                Color::Black => Color::White,
+            };
+            for (pos, gate) in parse_gates(gates, just_moved)? {
+                board[pos.row][pos.col].gate = Some(gate);
+            }
+            check_portals(&board)?;
+        }
+        let (castling_rights, castling_files) = parse_castling(fields[2], &board)?;
+        let en_passant = parse_en_passant(fields[3], &board, current_turn)?;
+        let halfmove_clock = fields[4].parse().map_err(|_| FenError::BadClock)?;
+        let fullmove_number = fields[5].parse().map_err(|_| FenError::BadClock)?;
+        let gate_stock = match fields.get(7) {
+            Some(field) => parse_gate_stock(field)?,
+            None => [0; 2],
+        };
+
+        let mut game = Game::new(board, gate_rules);
+        game.current_turn = current_turn;
+        game.castling_rights = castling_rights;
+        game.castling_files = castling_files;
+        game.en_passant = en_passant;
+        game.halfmove_clock = halfmove_clock;
+        game.fullmove_number = fullmove_number;
+        game.gate_stock = gate_stock;
+        game.hash = game.compute_hash();
+        game.position_history = vec![game.position_key()];
+        game.start_fen = game.to_fen();
+        game.check_game_result();
+
+        Ok(game)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut placement = String::new();
+        for row in (0..BOARD_SIZE).rev() {
+            let mut empty = 0;
+            for col in 0..BOARD_SIZE {
+                match self.board[row][col].piece {
+                    Some(piece) => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(piece_char(piece));
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if row > 0 {
+                placement.push('/');
+            }
+        }
+
+        let turn = match self.current_turn {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+
+        let rights = self.castling_rights;
+        let files = self.castling_files;
+        let mut castling = String::new();
+        for (has, color, side) in [
+            (rights.white_kingside, Color::White, CastlingSide::Kingside),
+            (rights.white_queenside, Color::White, CastlingSide::Queenside),
+            (rights.black_kingside, Color::Black, CastlingSide::Kingside),
+            (rights.black_queenside, Color::Black, CastlingSide::Queenside),
+        ] {
+            if !has {
+                continue;
+            }
+            let c = match side {
+                _ if files != CastlingFiles::STANDARD => (b'A' + files.rook(side) as u8) as char,
+                CastlingSide::Kingside => 'K',
+                CastlingSide::Queenside => 'Q',
+            };
+            castling.push(match color {
+                Color::White => c,
+                Color::Black => c.to_ascii_lowercase(),
+            });
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        let en_passant = self
+            .en_passant
+            .map(|pos| pos.to_algebraic())
+            .unwrap_or_else(|| "-".to_string());
+
+        let mut fen = format!(
+            "{} {} {} {} {} {}",
+            placement, turn, castling, en_passant, self.halfmove_clock, self.fullmove_number
+        );
+
+        let mut gates = Vec::new();
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                if let Some(gate) = self.board[row][col].gate {
+                    gates.push(format!("{}:{}", Position { row, col }.to_algebraic(), gate_text(gate)));
+                }
+            }
+        }
+        let placing = self.gate_stock != [0; 2];
+        if !gates.is_empty() || placing {
+            fen.push(' ');
+            fen.push_str(&gates.join(","));
+        }
+        if gates.is_empty() && placing {
+            fen.push('-');
+        }
+        if placing {
+            fen.push_str(&format!(" {}/{}", self.gate_stock[0], self.gate_stock[1]));
+        }
+
+        fen
+    }
+}
+
+fn piece_char(piece: Piece) -> char {
+    let c = piece.kind.to_char();
+    match piece.color {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+fn color_char(color: Color) -> char {
+    match color {
+        Color::White => 'w',
+        Color::Black => 'b',
+    }
+}
+
+fn gate_text(gate: GateType) -> String {
+    let owner = color_char(gate.owner());
+    match gate {
+        GateType::Standard { duration, .. } => format!("{}{}", duration, owner),
+        GateType::Wall { .. } => format!("#{}", owner),
+        GateType::OneWay {
+            duration, toward, ..
+        } => format!("{}{}{}", duration, owner, toward.to_char()),
+        GateType::Portal { duration, exit, .. } => {
+            format!("{}{}@{}", duration, owner, exit.to_algebraic())
+        }
+    }
+}
+
+fn parse_board(placement: &str) -> Result<[[Square; BOARD_SIZE]; BOARD_SIZE], FenError> {
+    let mut board = [[Square::new(); BOARD_SIZE]; BOARD_SIZE];
+
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != BOARD_SIZE {
+        return Err(FenError::BadBoard);
+    }
+
+    let mut kings = [0; 2];
+    // FEN lists the eighth rank first
+    for (i, rank) in ranks.iter().enumerate() {
+        let row = BOARD_SIZE - 1 - i;
+        let mut col = 0;
+        for c in rank.chars() {
+            if let Some(skip) = c.to_digit(10) {
+                col += skip as usize;
+                continue;
+            }
+            if col >= BOARD_SIZE {
+                return Err(FenError::BadBoard);
+            }
+            let kind = PieceType::from_char(c).ok_or(FenError::BadBoard)?;
+            let color = if c.is_ascii_uppercase() {
+                Color::White
+            } else {
+                Color::Black
+            };
+            if kind == PieceType::King {
+                kings[color as usize] += 1;
+            }
+            board[row][col].piece = Some(Piece::new(kind, color));
+            col += 1;
+        }
+        if col != BOARD_SIZE {
+            return Err(FenError::BadBoard);
+        }
+    }
+
+    if kings != [1, 1] {
+        return Err(FenError::BadKings);
+    }
+
+    Ok(board)
+}
+
+/// Reads the castling rights and, from where the kings and the rooks they
+/// name stand, the files castling starts from. `K` and `Q` with the king off
+/// its back rank are kept as plain rights that can never be used, as they
+/// always have been.
+fn parse_castling(
+    field: &str,
+    board: &[[Square; BOARD_SIZE]; BOARD_SIZE],
+) -> Result<(CastlingRights, CastlingFiles), FenError> {
+    let mut rights = CastlingRights {
+        white_kingside: false,
+        white_queenside: false,
+        black_kingside: false,
+        black_queenside: false,
+    };
+    let mut files = CastlingFiles::STANDARD;
+    if field == "-" {
+        return Ok((rights, files));
+    }
+
+    // Files seen so far. Both sides have to agree on them.
+    let mut king = None;
+    let mut rooks = [None; 2];
+    let settle = |slot: &mut Option<usize>, col: usize| match slot {
+        Some(seen) if *seen != col => Err(FenError::BadCastling),
+        _ => {
+            *slot = Some(col);
+            Ok(())
+        }
+    };
+
+    for c in field.chars() {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let row = back_rank(color);
+        let is_rook = |col: &usize| {
+            matches!(board[row][*col].piece,
+                Some(p) if p.kind == PieceType::Rook && p.color == color)
+        };
+        let king_col = (0..BOARD_SIZE).find(|&col| {
+            matches!(board[row][col].piece,
+                Some(p) if p.kind == PieceType::King && p.color == color)
+        });
+
+        let (side, rook_col) = match (c.to_ascii_lowercase(), king_col) {
+            ('k', None) => (CastlingSide::Kingside, None),
+            ('q', None) => (CastlingSide::Queenside, None),
+            ('k', Some(king)) => (CastlingSide::Kingside, (king + 1..BOARD_SIZE).rev().find(is_rook)),
+            ('q', Some(king)) => (CastlingSide::Queenside, (0..king).find(is_rook)),
+            (file @ 'a'..='h', Some(king)) => {
+                let col = (file as u8 - b'a') as usize;
+                if !is_rook(&col) || col == king {
+                    return Err(FenError::BadCastling);
+                }
+                let side = if col > king {
+                    CastlingSide::Kingside
+                } else {
+                    CastlingSide::Queenside
+                };
+                (side, Some(col))
+            }
+            _ => return Err(FenError::BadCastling),
+        };
+
+        if let (Some(king_col), Some(rook_col)) = (king_col, rook_col) {
+            settle(&mut king, king_col)?;
+            settle(&mut rooks[side as usize], rook_col)?;
+        }
+        match (color, side) {
+            (Color::White, CastlingSide::Kingside) => rights.white_kingside = true,
+            (Color::White, CastlingSide::Queenside) => rights.white_queenside = true,
+            (Color::Black, CastlingSide::Kingside) => rights.black_kingside = true,
+            (Color::Black, CastlingSide::Queenside) => rights.black_queenside = true,
+        }
+    }
+
+    if let Some(king) = king {
+        files.king = king;
+    }
+    if let Some(col) = rooks[CastlingSide::Kingside as usize] {
+        files.kingside_rook = col;
+    }
+    if let Some(col) = rooks[CastlingSide::Queenside as usize] {
+        files.queenside_rook = col;
+    }
+    Ok((rights, files))
+}
+
+/// Reads the en passant square, which must be the empty square an enemy
+/// pawn just skipped over: on the sixth rank with White to move, or the
+/// third with Black to move.
+fn parse_en_passant(
+    field: &str,
+    board: &[[Square; BOARD_SIZE]; BOARD_SIZE],
+    current_turn: Color,
+) -> Result<Option<Position>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+
+    let pos = Position::from_algebraic(field).ok_or(FenError::BadEnPassant)?;
+    let (row, pushed_row, pushed) = match current_turn {
+        Color::White => (BLACK_PAWN_ROW - 1, BLACK_PAWN_ROW - 2, Color::Black),
+        Color::Black => (WHITE_PAWN_ROW + 1, WHITE_PAWN_ROW + 2, Color::White),
+    };
+    let pawn_pushed = matches!(board[pushed_row][pos.col].piece,
+        Some(p) if p.kind == PieceType::Pawn && p.color == pushed);
+    if pos.row != row || board[pos.row][pos.col].piece.is_some() || !pawn_pushed {
+        return Err(FenError::BadEnPassant);
+    }
+    Ok(Some(pos))
+}
+
+/// Gates listed without an owner are given `default_owner`.
+fn parse_gates(field: &str, default_owner: Color) -> Result<Vec<(Position, GateType)>, FenError> {
+    let mut gates = Vec::new();
+    if field == "-" {
+        return Ok(gates);
+    }
+
+    for entry in field.split(',') {
+        let (square, rest) = entry.split_once(':').ok_or(FenError::BadGates)?;
+        let pos = Position::from_algebraic(square).ok_or(FenError::BadGates)?;
+        gates.push((pos, parse_gate(rest, default_owner)?));
+    }
+    Ok(gates)
+}
+
+/// Reads what follows the colon in one gate entry.
+fn parse_gate(text: &str, default_owner: Color) -> Result<GateType, FenError> {
+    let (text, exit) = match text.split_once('@') {
+        Some((text, exit)) => (
+            text,
+            Some(Position::from_algebraic(exit).ok_or(FenError::BadGates)?),
+        ),
+        None => (text, None),
+    };
+    let digits = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (duration, rest) = text.split_at(digits);
+    let mut rest = rest.chars();
+    let mut next = rest.next();
+
+    let wall = next == Some('#') && duration.is_empty();
+    if wall {
+        next = rest.next();
+    }
+    let owner = match next {
+        Some('w') => Color::White,
+        Some('b') => Color::Black,
+        _ => default_owner,
+    };
+    if matches!(next, Some('w' | 'b')) {
+        next = rest.next();
+    }
+    let toward = match next {
+        Some(c) => Some(Direction::from_char(c).ok_or(FenError::BadGates)?),
+        None => None,
+    };
+    if rest.next().is_some() {
+        return Err(FenError::BadGates);
+    }
+
+    if wall {
+        return match (toward, exit) {
+            (None, None) => Ok(GateType::Wall { owner }),
+            _ => Err(FenError::BadGates),
+        };
+    }
+    let duration: u8 = duration.parse().map_err(|_| FenError::BadGates)?;
+    if duration == 0 {
+        return Err(FenError::BadGates);
+    }
+    match (toward, exit) {
+        (None, None) => Ok(GateType::Standard { duration, owner }),
+        (Some(toward), None) => Ok(GateType::OneWay {
+            duration,
+            owner,
+            toward,
+        }),
+        (None, Some(exit)) => Ok(GateType::Portal {
+            duration,
+            owner,
+            exit,
+        }),
+        (Some(_), Some(_)) => Err(FenError::BadGates),
+    }
+}
+
+/// Reads the gates White and Black have left to place, such as `3/2`.
+fn parse_gate_stock(field: &str) -> Result<[u8; 2], FenError> {
+    let (white, black) = field.split_once('/').ok_or(FenError::BadGateStock)?;
+    let mut stock = [0; 2];
+    for (side, text) in [white, black].into_iter().enumerate() {
+        stock[side] = text.parse().map_err(|_| FenError::BadGateStock)?;
+    }
+    Ok(stock)
+}
+
+/// Each portal must lead to another portal that leads back, and nothing may
+/// stand on either end.
+fn check_portals(board: &[[Square; BOARD_SIZE]; BOARD_SIZE]) -> Result<(), FenError> {
+    for row in 0..BOARD_SIZE {
+        for col in 0..BOARD_SIZE {
+            let square = &board[row][col];
+            let Some(GateType::Portal { exit, .. }) = square.gate else {
+                continue;
+            };
+            let here = Position { row, col };
+            let paired = matches!(
+                board[exit.row][exit.col].gate,
+                Some(GateType::Portal { exit: back, .. }) if back == here && exit != here
+            );
+            if !paired || square.piece.is_some() {
+                return Err(FenError::BadGates);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::algebraic::parse_move;
+
+    #[test]
+    fn en_passant_square_must_follow_a_double_push() {
+        let pushed = "rnbqkbnr/pppp1ppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        assert!(Game::from_fen(pushed).is_ok());
+        for bad in [
+            // The wrong rank for the side to move
+            "rnbqkbnr/pppp1ppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR w KQkq e3 0 1",
+            // No pawn in front of it
+            "rnbqkbnr/pppp1ppp/8/8/3p4/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            // Not empty
+            "rnbqkbnr/pppp1ppp/8/8/3pP3/4N3/PPPP1PPP/R1BQKBNR b KQkq e3 0 1",
+            // Behind White's own unmoved pawn, with White to move
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1",
+        ] {
+            assert_eq!(Game::from_fen(bad).err(), Some(FenError::BadEnPassant), "{}", bad);
+        }
+
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        game.make_move(parse_move("e2e4").unwrap()).unwrap();
+        assert!(Game::from_fen(&game.to_fen()).is_ok());
+    }
+
+    #[test]
+    fn gates_and_stock_round_trip() {
+        let fen = "4k3/8/8/8/8/8/8/4K3 b - - 3 7 c1:2b,h3:3b@e6,a4:#w,d4:1w^,e6:3b@h3 2/1";
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.gate_stock, [2, 1]);
+        assert_eq!(game.to_fen(), fen);
+
+        let placing = "4k3/8/8/8/8/8/8/4K3 w - - 0 1 - 3/0";
+        assert_eq!(Game::from_fen(placing).unwrap().to_fen(), placing);
+    }
+}
@@ -0,0 +1,664 @@
+// =======================================================
+// Project: GatedChess
+// File: game.rs
+// Description: Defines game rules.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2025-11-07
+// License: MIT
+// =======================================================
+
+pub mod attacks;
+pub mod bitboard;
+pub mod draw_rules;
+pub mod events;
+pub mod fen;
+pub mod moves;
+pub mod perft;
+pub mod setups;
+pub mod state_machine;
+pub mod undo;
+pub mod zobrist;
+
+pub use moves::move_type::Move;
+
+use crate::board::Square;
+use crate::config::BOARD_SIZE;
+use crate::pieces::piece_fsm::PieceState;
+use crate::pieces::{Color, Piece, PieceType};
+
+use crate::game::bitboard::{Bitboards, squares};
+use crate::game::events::GameEvent;
+use crate::game::moves::castling::{CastlingFiles, CastlingRights};
+use crate::game::state_machine::GameStateManager;
+use crate::game::undo::Undo;
+use crate::gates::{GateRules, GateType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    /// Square name such as `e4`.
+    pub fn to_algebraic(self) -> String {
+        format!("{}{}", (b'a' + self.col as u8) as char, self.row + 1)
+    }
+
+    pub fn from_algebraic(square: &str) -> Option<Position> {
+        let mut chars = square.chars();
+        let file = chars.next()?.to_ascii_lowercase();
+        let rank = chars.next()?.to_digit(10)? as usize;
+        if chars.next().is_some()
+            || !('a'..='h').contains(&file)
+            || !(1..=BOARD_SIZE).contains(&rank)
+        {
+            return None;
+        }
+
+        Some(Position {
+            row: rank - 1,
+            col: (file as u8 - b'a') as usize,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveError {
+    IllegalMove,
+    /// The move would leave the mover's own king attacked.
+    MustGetOutOfCheck,
+    GameNotInProgress,
+    /// A pawn reaching the last rank needs a piece to promote to, and no
+    /// other move may carry one.
+    InvalidPromotion,
+    /// The side to move has no gates left to place.
+    NoGatesLeft,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameResult {
+    Checkmate(Color),
+    Stalemate,
+    FiftyMoveRule,
+    ThreefoldRepetition,
+    InsufficientMaterial,
+    InProgress,
+}
+
+pub struct Game {
+    pub board: [[Square; BOARD_SIZE]; BOARD_SIZE],
+    /// Same position as `board`, kept in step by `set_piece` and `set_gate`.
+    pub(crate) bitboards: Bitboards,
+    pub state_manager: GameStateManager,
+    pub current_turn: Color,
+    pub result: GameResult,
+    pub castling_rights: CastlingRights,
+    /// Where kings and rooks start, for castling.
+    pub castling_files: CastlingFiles,
+    /// Square a pawn skipped over with a double push on the previous move.
+    pub en_passant: Option<Position>,
+    /// Halfmoves since the last pawn move or capture.
+    pub halfmove_clock: u32,
+    /// Starts at 1 and goes up after each Black move.
+    pub fullmove_number: u32,
+    /// Position the game started from, used to replay `move_list`.
+    start_fen: String,
+    /// Zobrist hash of the position, updated as squares and rules change.
+    hash: u64,
+    /// `position_key` of every position reached so far, current one last.
+    position_history: Vec<u64>,
+    undo_stack: Vec<Undo>,
+    redo_stack: Vec<Move>,
+    /// Which moves leave gates, where and for how long.
+    pub(crate) gate_rules: GateRules,
+    /// Gates each side has left to place, indexed by `Color as usize`.
+    pub(crate) gate_stock: [u8; 2],
+    /// Queued for `drain_events`.
+    events: Vec<GameEvent>,
+}
+
+impl Game {
+    pub fn new(mut board: [[Square; BOARD_SIZE]; BOARD_SIZE], gate_rules: GateRules) -> Game {
+        let mut state_manager = GameStateManager::new();
+        state_manager.register_all_pieces(&mut board);
+
+        let mut game = Game {
+            bitboards: Bitboards::from_board(&board, gate_rules.blocks_owner),
+            board,
+            state_manager,
+            current_turn: Color::White,
+            result: GameResult::InProgress,
+            castling_rights: CastlingRights::all(),
+            castling_files: CastlingFiles::STANDARD,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            start_fen: String::new(),
+            hash: 0,
+            position_history: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            gate_rules,
+            gate_stock: [gate_rules.placement_stock; 2],
+            events: Vec::new(),
+        };
+        game.hash = game.compute_hash();
+        game.position_history.push(game.position_key());
+        game.start_fen = game.to_fen();
+        game
+    }
+
+    pub fn get_all_legal_moves(&self, color: Color) -> Vec<Move> {
+        use moves::generation::get_piece_moves;
+        let mut all_moves = Vec::new();
+
+        for pos in squares(self.bitboards.colors[color as usize]) {
+            // Get moves for this piece without checking current_turn
+            let mut targets = get_piece_moves(self, pos, color);
+            // Filter out moves that leave king in check
+            targets.retain(|&to| !self.move_exposes_king(pos, to, color));
+
+            for to in targets {
+                if self.is_promotion(pos, to) {
+                    for kind in PieceType::PROMOTIONS {
+                        all_moves.push(self.describe_move(pos, to, Some(kind)));
+                    }
+                } else {
+                    all_moves.push(self.describe_move(pos, to, None));
+                }
+            }
+        }
+        all_moves.extend(self.gate_placements(color).into_iter().map(Move::place_gate));
+        all_moves
+    }
+
+    /// Plays `mv` for the side to move and returns the full record of what
+    /// happened. Only `from`, `to`, `promotion` and `places_gate` are read
+    /// from `mv`.
+    pub fn make_move(&mut self, mv: Move) -> Result<Move, MoveError> {
+        let (from, to, promotion) = (mv.from, mv.to, mv.promotion);
+
+        if self.result != GameResult::InProgress {
+            return Err(MoveError::GameNotInProgress);
+        }
+        if mv.places_gate {
+            self.check_placement(to, self.current_turn)?;
+            let played = self.place_gate_unchecked(to);
+            self.finish_move(&played);
+            return Ok(played);
+        }
+        // Check the piece can make the move at all, then that it keeps the
+        // king safe
+        let own_piece = self.board[from.row][from.col]
+            .piece
+            .is_some_and(|p| p.color == self.current_turn);
+        let reachable = own_piece
+            && moves::generation::get_piece_moves(self, from, self.current_turn).contains(&to);
+        if !reachable {
+            if own_piece {
+                self.record_blocked_move(from, to);
+            }
+            return Err(MoveError::IllegalMove);
+        }
+        if self.leaves_king_in_check(from, to, self.current_turn) {
+            return Err(MoveError::MustGetOutOfCheck);
+        }
+        let promotion_valid = match promotion {
+            Some(kind) => self.is_promotion(from, to) && PieceType::PROMOTIONS.contains(&kind),
+            None => !self.is_promotion(from, to),
+        };
+        if !promotion_valid {
+            return Err(MoveError::InvalidPromotion);
+        }
+
+        let played = self.make_move_unchecked(from, to, promotion);
+        self.finish_move(&played);
+        Ok(played)
+    }
+
+    /// Catches FSMs, redo history, the result and the event queue up with a
+    /// move `make_move` has just played.
+    fn finish_move(&mut self, played: &Move) {
+        self.sync_fsms(played);
+        self.redo_stack.clear();
+
+        self.check_game_result();
+
+        self.state_manager.update_all_fsm(&mut self.board);
+        self.record_move_events(played);
+    }
+
+    pub fn switch_turn(&mut self) {
+        self.current_turn = match self.current_turn {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+    }
+
+    pub fn get_legal_moves(&self, pos: Position) -> Vec<Position> {
+        use moves::generation::get_piece_moves;
+
+        if let Some(piece) = self.board[pos.row][pos.col].piece {
+            if piece.color != self.current_turn {
+                return Vec::new();
+            }
+
+            let mut moves = get_piece_moves(self, pos, piece.color);
+
+            // Filter out moves that leave king in check
+            moves.retain(|&to| !self.move_exposes_king(pos, to, piece.color));
+
+            moves
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub(crate) fn apply_offset(&self, pos: Position, dr: i32, dc: i32) -> Option<Position> {
+        let new_row = pos.row as i32 + dr;
+        let new_col = pos.col as i32 + dc;
+
+        if new_row >= 0
+            && new_row < BOARD_SIZE as i32
+            && new_col >= 0
+            && new_col < BOARD_SIZE as i32
+        {
+            Some(Position {
+                row: new_row as usize,
+                col: new_col as usize,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn gate_rules(&self) -> GateRules {
+        self.gate_rules
+    }
+
+    /// Changes how gates behave from the next move on, and refills both
+    /// sides' stock of gates to place. Meant for setting up a game before it
+    /// starts, such as when the host's rules arrive.
+    pub fn set_gate_rules(&mut self, gate_rules: GateRules) {
+        self.gate_rules = gate_rules;
+        self.bitboards.blocks_owner = gate_rules.blocks_owner;
+        self.gate_stock = [gate_rules.placement_stock; 2];
+
+        self.hash = self.compute_hash();
+        if let Some(key) = self.position_history.last_mut() {
+            *key = self.hash;
+        }
+        if self.undo_stack.is_empty() {
+            self.start_fen = self.to_fen();
+        }
+        // Different gates can free a king the old ones had mated
+        self.result = GameResult::InProgress;
+        self.check_game_result();
+    }
+
+    /// Gates `color` has left to place.
+    pub fn gate_stock(&self, color: Color) -> u8 {
+        self.gate_stock[color as usize]
+    }
+
+    /// Empty squares without a gate where `color` may place one now.
+    pub fn gate_placements(&self, color: Color) -> Vec<Position> {
+        if self.gate_stock[color as usize] == 0 {
+            return Vec::new();
+        }
+        let open = !(self.bitboards.occupied() | self.bitboards.gates);
+        squares(open)
+            .filter(|&square| !self.placement_exposes_king(square, color))
+            .collect()
+    }
+
+    /// Same position as `board`, as bitboards.
+    pub fn bitboards(&self) -> &Bitboards {
+        &self.bitboards
+    }
+
+    /// Returns true if `color`'s king is attacked, gates and all. This is
+    /// the fast form of asking whether `checkers` is empty.
+    pub fn is_king_in_check(&self, color: Color) -> bool {
+        // Find the king
+        let king_pos = self.find_king(color);
+        if king_pos.is_none() {
+            return false;
+        }
+        let king_pos = king_pos.unwrap();
+
+        // Check if any opponent piece can attack the king
+        let opponent_color = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+
+        self.is_square_attacked(king_pos, opponent_color)
+    }
+
+    pub fn is_checkmate(&self, color: Color) -> bool {
+        // Checkmate: king is in check AND has no legal moves
+        self.is_king_in_check(color) && self.get_all_legal_moves(color).is_empty()
+    }
+
+    pub fn is_stalemate(&self, color: Color) -> bool {
+        // Stalemate: king is NOT in check BUT has no legal moves
+        !self.is_king_in_check(color) && self.get_all_legal_moves(color).is_empty()
+    }
+
+    pub fn check_game_result(&mut self) {
+        // Check the state of the game after each move, from the side of the
+        // player who now has to reply
+        let to_move = self.current_turn;
+        let previous_player = match to_move {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+
+        if self.is_checkmate(to_move) {
+            self.result = GameResult::Checkmate(previous_player);
+        } else if self.is_stalemate(to_move) {
+            self.result = GameResult::Stalemate;
+        } else if self.is_insufficient_material() {
+            self.result = GameResult::InsufficientMaterial;
+        } else if self.halfmove_clock >= draw_rules::FIFTY_MOVE_LIMIT {
+            self.result = GameResult::FiftyMoveRule;
+        } else if self.repetition_count() >= 3 {
+            self.result = GameResult::ThreefoldRepetition;
+        }
+    }
+
+    fn find_king(&self, color: Color) -> Option<Position> {
+        self.bitboards.king_square(color)
+    }
+
+    pub(crate) fn get_path_between(&self, from: Position, to: Position) -> Vec<Position> {
+        let mut path = Vec::new();
+
+        let dr = to.row as i32 - from.row as i32;
+        let dc = to.col as i32 - from.col as i32;
+
+        if dr == 0 || dc == 0 || dr.abs() == dc.abs() {
+            let steps = dr.abs().max(dc.abs()) as usize;
+
+            if steps > 1 {
+                let dr_step = if dr != 0 { dr / dr.abs() } else { 0 };
+                let dc_step = if dc != 0 { dc / dc.abs() } else { 0 };
+
+                for i in 1..steps {
+                    if let Some(pos) =
+                        self.apply_offset(from, dr_step * i as i32, dc_step * i as i32)
+                    {
+                        path.push(pos);
+                    }
+                }
+            }
+        }
+        path
+    }
+
+    /// Plays `mv` with `make_move_unchecked` or `place_gate_unchecked`,
+    /// whichever it asks for.
+    pub fn play_unchecked(&mut self, mv: &Move) -> Move {
+        if mv.places_gate {
+            self.place_gate_unchecked(mv.to)
+        } else {
+            self.make_move_unchecked(mv.from, mv.to, mv.promotion)
+        }
+    }
+
+    /// Spends the turn placing a gate of the side to move on `square`,
+    /// without validating it. Revert it with `unmake_move_unchecked`.
+    pub fn place_gate_unchecked(&mut self, square: Position) -> Move {
+        let mut record = Move::place_gate(square);
+        self.push_undo(&record);
+        self.hash ^= self.current_rules_key();
+
+        self.halfmove_clock += 1;
+        self.en_passant = None;
+        let gate = GateType::Standard {
+            duration: self.gate_rules.duration,
+            owner: self.current_turn,
+        };
+        self.set_gate(square, Some(gate));
+        let stock = &mut self.gate_stock[self.current_turn as usize];
+        *stock = stock.saturating_sub(1);
+        record.gates_created.push(square);
+
+        self.end_turn(&mut record);
+        record
+    }
+
+    /// Plays a move on the board and rules state without validating it or
+    /// touching piece FSMs, and switches the turn. Revert it with
+    /// `unmake_move_unchecked`.
+    pub fn make_move_unchecked(
+        &mut self,
+        from: Position,
+        to: Position,
+        promotion: Option<PieceType>,
+    ) -> Move {
+        let mut record = self.describe_move(from, to, promotion);
+        self.push_undo(&record);
+        // Rules keys come out now and go back in once turn, castling and en
+        // passant are updated
+        self.hash ^= self.current_rules_key();
+
+        // Get the piece before moving
+        let piece = self.board[from.row][from.col].piece;
+
+        self.update_castling_rights(from, to);
+
+        let is_pawn_move = matches!(piece, Some(p) if p.kind == PieceType::Pawn);
+        if is_pawn_move || record.captured.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        // The pawn taken en passant sits beside the destination, not on it
+        if let Some(captured_pos) = self.en_passant_capture_square(from, to) {
+            self.set_piece(captured_pos, None);
+        }
+
+        // A double pawn push leaves the skipped square open to en passant
+        self.en_passant = match piece {
+            Some(p) if p.kind == PieceType::Pawn && from.row.abs_diff(to.row) == 2 => {
+                Some(Position {
+                    row: (from.row + to.row) / 2,
+                    col: from.col,
+                })
+            }
+            _ => None,
+        };
+
+        // Castling also moves the rook, which spawns gates like any rook move.
+        // Outside standard chess king and rook may land on each other's
+        // squares, so both are lifted before either is put down.
+        let landing = record.landing();
+        if let Some(side) = record.castling {
+            let rook_from = Position {
+                row: from.row,
+                col: self.castling_files.rook(side),
+            };
+            let rook_to = Position {
+                row: from.row,
+                col: side.rook_target_col(),
+            };
+
+            let rook = self.board[rook_from.row][rook_from.col].piece;
+            self.set_piece(rook_from, None);
+            self.set_piece(from, None);
+            self.set_piece(rook_to, rook);
+            self.set_piece(landing, piece);
+            if let (Some(rook), true) = (rook, rook_from != rook_to) {
+                record.gates_created = self.spawn_gates(rook, rook_from, rook_to);
+            }
+        } else {
+            self.set_piece(to, piece);
+            self.set_piece(from, None);
+        }
+
+        // Swap the pawn for the promoted piece
+        if let (Some(pawn), Some(kind)) = (piece, promotion) {
+            self.set_piece(to, Some(Piece::new(kind, pawn.color)));
+        }
+
+        // Create gates behind the pieces the rules pick. A king that castles
+        // without moving leaves none.
+        if let (Some(p), true) = (piece, landing != from) {
+            let created = self.spawn_gates(p, from, landing);
+            record.gates_created.extend(created);
+        }
+
+        self.end_turn(&mut record);
+        record
+    }
+
+    /// Hands the turn over once a move is on the board: ages the gates,
+    /// then puts the rules key back into the hash.
+    fn end_turn(&mut self, record: &mut Move) {
+        record.gates_expired = crate::gates::logic::age_gates(self);
+        if self.current_turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.switch_turn();
+        self.hash ^= self.current_rules_key();
+        self.position_history.push(self.position_key());
+    }
+
+    /// Brings piece FSMs in line with a move just played. Captured FSMs are
+    /// kept on the undo stack so `unmake_move` can put them back.
+    fn sync_fsms(&mut self, played: &Move) {
+        let mut retired = Vec::new();
+        let manager = &mut self.state_manager;
+
+        if let Some(captured) = played.captured {
+            let captured_pos = if played.en_passant {
+                Position {
+                    row: played.from.row,
+                    col: played.to.col,
+                }
+            } else {
+                played.to
+            };
+            manager.piece_positions.remove(&captured.id);
+            if let Some(mut fsm) = manager.piece_fsms.remove(&captured.id) {
+                fsm.set_state(PieceState::Captured);
+                retired.push((fsm, captured_pos));
+            }
+        }
+
+        if let Some(piece) = played.piece {
+            manager.piece_positions.insert(piece.id, played.landing());
+        }
+
+        if let Some(side) = played.castling {
+            let rook_to = Position {
+                row: played.from.row,
+                col: side.rook_target_col(),
+            };
+            if let Some(rook) = self.board[rook_to.row][rook_to.col].piece {
+                manager.piece_positions.insert(rook.id, rook_to);
+            }
+        }
+
+        // The promoted piece gets its own FSM in place of the pawn's
+        if let (Some(pawn), Some(_)) = (played.piece, played.promotion) {
+            manager.piece_positions.remove(&pawn.id);
+            if let Some(fsm) = manager.piece_fsms.remove(&pawn.id) {
+                retired.push((fsm, played.from));
+            }
+            if let Some(promoted) = self.board[played.to.row][played.to.col].piece {
+                manager.register_piece(promoted, played.to);
+            }
+        }
+
+        if let Some(undo) = self.undo_stack.last_mut() {
+            undo.retired = retired;
+        }
+    }
+
+    /// Builds the full record for moving the piece on `from` to `to`, as seen
+    /// from the current board. Gate fields are filled in once it is played.
+    pub(crate) fn describe_move(
+        &self,
+        from: Position,
+        to: Position,
+        promotion: Option<PieceType>,
+    ) -> Move {
+        let en_passant_capture = self.en_passant_capture_square(from, to);
+        let captured_pos = en_passant_capture.unwrap_or(to);
+        let castling = self.castling_side(from, to);
+        // A king moving onto its own rook to castle takes nothing
+        let captured = match castling {
+            Some(_) => None,
+            None => self.board[captured_pos.row][captured_pos.col].piece,
+        };
+
+        Move {
+            piece: self.board[from.row][from.col].piece,
+            captured,
+            castling,
+            en_passant: en_passant_capture.is_some(),
+            ..Move::new(from, to).with_promotion(promotion)
+        }
+    }
+
+    /// Returns true if moving the piece on `from` to `to` takes a pawn to the
+    /// last rank, so the move needs a promotion choice.
+    pub fn is_promotion(&self, from: Position, to: Position) -> bool {
+        match self.board[from.row][from.col].piece {
+            Some(p) if p.kind == PieceType::Pawn => {
+                let last_rank = match p.color {
+                    Color::White => BOARD_SIZE - 1,
+                    Color::Black => 0,
+                };
+                to.row == last_rank
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the square of the pawn that would be removed if moving the
+    /// piece on `from` to `to` is an en passant capture. Only an opposing
+    /// pawn is ever removed.
+    pub(crate) fn en_passant_capture_square(
+        &self,
+        from: Position,
+        to: Position,
+    ) -> Option<Position> {
+        let piece = self.board[from.row][from.col].piece?;
+        if piece.kind != PieceType::Pawn || self.en_passant != Some(to) || from.col == to.col {
+            return None;
+        }
+
+        let captured = Position {
+            row: from.row,
+            col: to.col,
+        };
+        match self.board[captured.row][captured.col].piece {
+            Some(pawn) if pawn.kind == PieceType::Pawn && pawn.color != piece.color => {
+                Some(captured)
+            }
+            _ => None,
+        }
+    }
+
+    /// Leaves the gates `piece` makes by moving from `from` to `to`, once
+    /// the board shows the move.
+    fn spawn_gates(&mut self, piece: Piece, from: Position, to: Position) -> Vec<Position> {
+        let gated = self.gate_rules.gate_squares(piece.kind, from, to, self.bitboards.occupied());
+        let gate = GateType::Standard {
+            duration: self.gate_rules.duration,
+            owner: piece.color,
+        };
+        let created: Vec<Position> = squares(gated).collect();
+        for &pos in &created {
+            self.set_gate(pos, Some(gate));
+        }
+        created
+    }
+}
@@ -0,0 +1,316 @@
+// =======================================================
+// Project: GatedChess
+// File: castling.rs
+// Description: Castling rights and castling move generation.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Castling works the same for standard chess and Chess960: the king ends
+//! on the c- or g-file and the rook next to it on the d- or f-file, wherever
+//! they started. `CastlingFiles` records where that is. Standard games write
+//! a castle as the king moving two squares, as usual. Any other layout
+//! writes it as the king moving onto its own rook, since the king may move
+//! one square or not at all.
+
+use crate::config::BOARD_SIZE;
+use crate::game::bitboard::{Bitboard, square_bit, squares};
+use crate::game::{Game, Position};
+use crate::pieces::{Color, PieceType};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CastlingSide {
+    Kingside,
+    Queenside,
+}
+
+impl CastlingSide {
+    /// Column the king lands on after castling to this side.
+    pub fn king_target_col(&self) -> usize {
+        match self {
+            CastlingSide::Kingside => 6,
+            CastlingSide::Queenside => 2,
+        }
+    }
+
+    /// Column the rook lands on after castling to this side.
+    pub fn rook_target_col(&self) -> usize {
+        match self {
+            CastlingSide::Kingside => 5,
+            CastlingSide::Queenside => 3,
+        }
+    }
+}
+
+/// Files the king and castling rooks start on, the same for both sides.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CastlingFiles {
+    pub king: usize,
+    pub kingside_rook: usize,
+    pub queenside_rook: usize,
+}
+
+impl CastlingFiles {
+    pub const STANDARD: CastlingFiles = CastlingFiles {
+        king: 4,
+        kingside_rook: 7,
+        queenside_rook: 0,
+    };
+
+    /// The files in a back rank laid out like `back_rank`, taking the rooks
+    /// either side of the king.
+    pub fn from_back_rank(back_rank: &[PieceType; BOARD_SIZE]) -> Self {
+        let king = back_rank
+            .iter()
+            .position(|kind| *kind == PieceType::King)
+            .unwrap_or(Self::STANDARD.king);
+        let rook = |col: &usize| back_rank[*col] == PieceType::Rook;
+        CastlingFiles {
+            king,
+            kingside_rook: (king + 1..BOARD_SIZE).find(rook).unwrap_or(Self::STANDARD.kingside_rook),
+            queenside_rook: (0..king).find(rook).unwrap_or(Self::STANDARD.queenside_rook),
+        }
+    }
+
+    pub fn rook(&self, side: CastlingSide) -> usize {
+        match side {
+            CastlingSide::Kingside => self.kingside_rook,
+            CastlingSide::Queenside => self.queenside_rook,
+        }
+    }
+}
+
+/// Tracks which castles are still available. A right is lost for good once
+/// the king or the matching rook leaves its starting square, or the rook is
+/// captured there.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+impl CastlingRights {
+    pub fn all() -> Self {
+        Self {
+            white_kingside: true,
+            white_queenside: true,
+            black_kingside: true,
+            black_queenside: true,
+        }
+    }
+
+    pub fn has(&self, color: Color, side: CastlingSide) -> bool {
+        match (color, side) {
+            (Color::White, CastlingSide::Kingside) => self.white_kingside,
+            (Color::White, CastlingSide::Queenside) => self.white_queenside,
+            (Color::Black, CastlingSide::Kingside) => self.black_kingside,
+            (Color::Black, CastlingSide::Queenside) => self.black_queenside,
+        }
+    }
+
+    pub fn revoke(&mut self, color: Color, side: CastlingSide) {
+        match (color, side) {
+            (Color::White, CastlingSide::Kingside) => self.white_kingside = false,
+            (Color::White, CastlingSide::Queenside) => self.white_queenside = false,
+            (Color::Black, CastlingSide::Kingside) => self.black_kingside = false,
+            (Color::Black, CastlingSide::Queenside) => self.black_queenside = false,
+        }
+    }
+
+    pub fn revoke_all(&mut self, color: Color) {
+        self.revoke(color, CastlingSide::Kingside);
+        self.revoke(color, CastlingSide::Queenside);
+    }
+}
+
+pub fn back_rank(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 7,
+    }
+}
+
+impl Game {
+    pub(super) fn castling_moves(&self, pos: Position, color: Color) -> Vec<Position> {
+        let mut moves = Vec::new();
+
+        let files = self.castling_files;
+        let row = back_rank(color);
+        if pos.row != row || pos.col != files.king {
+            return moves;
+        }
+
+        let opponent = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+
+        // Castling out of check is never allowed
+        if self.is_square_attacked(pos, opponent) {
+            return moves;
+        }
+
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            if !self.castling_rights.has(color, side) {
+                continue;
+            }
+
+            let rook_pos = Position {
+                row,
+                col: files.rook(side),
+            };
+            match self.board[rook_pos.row][rook_pos.col].piece {
+                Some(rook) if rook.kind == PieceType::Rook && rook.color == color => {}
+                _ => continue,
+            }
+
+            // Every square the king or rook crosses or lands on must be empty
+            // but for the two of them, and a gate that blocks this side on any
+            // of them stops the castle just like a piece would. King and rook
+            // head in opposite directions, so a one-way gate along the way
+            // stops one of them.
+            let target = Position {
+                row,
+                col: side.king_target_col(),
+            };
+            let rook_target = Position {
+                row,
+                col: side.rook_target_col(),
+            };
+            let gates = self.bitboards.gates_blocking(color, pos)
+                | self.bitboards.gates_blocking(color, rook_pos);
+            let crossed = rank_span(pos, target) | rank_span(rook_pos, rook_target);
+            let path_clear = squares(crossed).all(|sq| {
+                sq == pos
+                    || sq == rook_pos
+                    || (self.board[sq.row][sq.col].piece.is_none() && gates & square_bit(sq) == 0)
+            });
+            if !path_clear {
+                continue;
+            }
+
+            // The king may not pass through or land on an attacked square
+            let mut king_path = self.get_path_between(pos, target);
+            king_path.push(target);
+            if king_path
+                .iter()
+                .any(|&sq| self.is_square_attacked(sq, opponent))
+            {
+                continue;
+            }
+
+            moves.push(if files == CastlingFiles::STANDARD {
+                target
+            } else {
+                rook_pos
+            });
+        }
+
+        moves
+    }
+
+    /// Returns the side being castled if moving the piece on `from` to `to`
+    /// is a castling move.
+    pub(crate) fn castling_side(&self, from: Position, to: Position) -> Option<CastlingSide> {
+        let piece = self.board[from.row][from.col].piece?;
+        let files = self.castling_files;
+        if piece.kind != PieceType::King
+            || from.row != to.row
+            || from.row != back_rank(piece.color)
+            || from.col != files.king
+        {
+            return None;
+        }
+
+        if files == CastlingFiles::STANDARD {
+            return match to.col as i32 - from.col as i32 {
+                2 => Some(CastlingSide::Kingside),
+                -2 => Some(CastlingSide::Queenside),
+                _ => None,
+            };
+        }
+        let rook = self.board[to.row][to.col].piece?;
+        if rook.kind != PieceType::Rook || rook.color != piece.color {
+            return None;
+        }
+        [CastlingSide::Kingside, CastlingSide::Queenside]
+            .into_iter()
+            .find(|side| files.rook(*side) == to.col)
+    }
+
+    /// Clears castling rights affected by a move from `from` to `to`. Must be
+    /// called before the board is updated.
+    pub(crate) fn update_castling_rights(&mut self, from: Position, to: Position) {
+        if let Some(piece) = self.board[from.row][from.col].piece
+            && piece.kind == PieceType::King
+        {
+            self.castling_rights.revoke_all(piece.color);
+        }
+
+        // A rook leaving its corner, or being captured on it
+        for pos in [from, to] {
+            for color in [Color::White, Color::Black] {
+                if pos.row != back_rank(color) {
+                    continue;
+                }
+                for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+                    if pos.col == self.castling_files.rook(side) {
+                        self.castling_rights.revoke(color, side);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Squares from `from` to `to` along a rank, both ends included.
+fn rank_span(from: Position, to: Position) -> Bitboard {
+    let (low, high) = (from.col.min(to.col), from.col.max(to.col));
+    (low..=high).fold(0, |span, col| span | square_bit(Position { row: from.row, col }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::algebraic::parse_move;
+
+    fn king_targets(fen: &str) -> Vec<String> {
+        let game = Game::from_fen(fen).unwrap();
+        let king = game.bitboards().king_square(game.current_turn).unwrap();
+        game.get_legal_moves(king)
+            .iter()
+            .map(|pos| pos.to_algebraic())
+            .collect()
+    }
+
+    #[test]
+    fn gates_block_castling_through_and_into_them() {
+        let board = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let open = king_targets(board);
+        assert!(open.contains(&"g1".to_string()) && open.contains(&"c1".to_string()));
+
+        // The king crosses f1, lands on g1, and the queenside rook crosses b1
+        for (gate, blocked, open) in [("f1", "g1", "c1"), ("g1", "g1", "c1"), ("b1", "c1", "g1")] {
+            let targets = king_targets(&format!("{} {}:2b", board, gate));
+            assert!(!targets.contains(&blocked.to_string()), "gate on {}", gate);
+            assert!(targets.contains(&open.to_string()), "gate on {}", gate);
+        }
+    }
+
+    #[test]
+    fn chess960_castles_by_moving_the_king_onto_its_rook() {
+        let mut game = Game::from_fen("1k6/8/8/8/8/8/8/RK2R3 w EA - 0 1").unwrap();
+        assert!(game.make_move(parse_move("b1g1").unwrap()).is_err());
+
+        let played = game.make_move(parse_move("b1e1").unwrap()).unwrap();
+        assert_eq!(played.castling, Some(CastlingSide::Kingside));
+        assert_eq!(game.to_fen(), "1k6/8/8/8/8/8/8/R4RK1 b - - 1 1");
+
+        game.unmake_move();
+        assert_eq!(game.to_fen(), "1k6/8/8/8/8/8/8/RK2R3 w EA - 0 1");
+    }
+}
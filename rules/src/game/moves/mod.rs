@@ -1,3 +1,4 @@
+pub mod castling;
 pub mod generation;
-pub mod piece_moves;
+pub mod move_type;
 pub mod validation;
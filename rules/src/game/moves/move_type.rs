@@ -0,0 +1,88 @@
+// =======================================================
+// Project: GatedChess
+// File: move_type.rs
+// Description: Defines the move record shared by rules, UI and network.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::Position;
+use crate::game::moves::castling::CastlingSide;
+use crate::pieces::{Piece, PieceType};
+
+/// A single move. Input code (clicks, typed moves, network lines) builds a
+/// bare move with `Move::new` or `Move::place_gate`, and the rules fill in
+/// the rest when the move is generated or played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+    pub promotion: Option<PieceType>,
+    /// The piece that moved, if known.
+    pub piece: Option<Piece>,
+    pub captured: Option<Piece>,
+    pub castling: Option<CastlingSide>,
+    pub en_passant: bool,
+    /// The turn was spent placing a gate on `to` instead of moving a piece.
+    /// `from` is the same square.
+    pub places_gate: bool,
+    /// Squares that received a gate when the move was played.
+    pub gates_created: Vec<Position>,
+    /// Squares whose gate ran out when the move was played.
+    pub gates_expired: Vec<Position>,
+}
+
+impl Move {
+    pub fn new(from: Position, to: Position) -> Move {
+        Move {
+            from,
+            to,
+            promotion: None,
+            piece: None,
+            captured: None,
+            castling: None,
+            en_passant: false,
+            places_gate: false,
+            gates_created: Vec::new(),
+            gates_expired: Vec::new(),
+        }
+    }
+
+    /// Spending the turn on a gate at `square`, for games whose rules give
+    /// each side a stock of them.
+    pub fn place_gate(square: Position) -> Move {
+        Move {
+            places_gate: true,
+            ..Move::new(square, square)
+        }
+    }
+
+    /// Square the moving piece ends up on. That is `to` for every move but
+    /// castling outside standard chess, which is written as the king moving
+    /// onto its own rook.
+    pub fn landing(&self) -> Position {
+        match self.castling {
+            Some(side) => Position {
+                row: self.from.row,
+                col: side.king_target_col(),
+            },
+            None => self.to,
+        }
+    }
+
+    pub fn with_promotion(mut self, promotion: Option<PieceType>) -> Move {
+        self.promotion = promotion;
+        self
+    }
+
+    /// True if both moves describe the same action on the board, regardless
+    /// of how much of the record has been filled in.
+    pub fn matches(&self, other: &Move) -> bool {
+        self.from == other.from
+            && self.to == other.to
+            && self.promotion == other.promotion
+            && self.places_gate == other.places_gate
+    }
+}
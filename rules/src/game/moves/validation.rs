@@ -0,0 +1,62 @@
+// =======================================================
+// Project: GatedChess
+// File: validation.rs
+// Description: Validates moves and checks.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::bitboard::square_bit;
+use crate::game::{Game, MoveError, Position};
+use crate::pieces::Color;
+
+impl Game {
+    pub fn leaves_king_in_check(&self, from: Position, to: Position, color: Color) -> bool {
+        self.move_exposes_king(from, to, color)
+    }
+
+    /// Checks that `color` may place a gate on `square`: they have one left,
+    /// the square is empty and without a gate, and their king is not left
+    /// attacked.
+    pub fn check_placement(&self, square: Position, color: Color) -> Result<(), MoveError> {
+        if self.gate_stock[color as usize] == 0 {
+            return Err(MoveError::NoGatesLeft);
+        }
+        if (self.bitboards.occupied() | self.bitboards.gates) & square_bit(square) != 0 {
+            return Err(MoveError::IllegalMove);
+        }
+        if self.placement_exposes_king(square, color) {
+            return Err(MoveError::MustGetOutOfCheck);
+        }
+        Ok(())
+    }
+
+    /// Returns true if any piece of `by` attacks `target`. Kings only count
+    /// their one-square steps here, so castling never recurses into this.
+    pub(crate) fn is_square_attacked(&self, target: Position, by: Color) -> bool {
+        self.bitboards.is_attacked(target, by)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{Game, MoveError};
+    use crate::parser::algebraic::parse_move;
+
+    #[test]
+    fn moves_that_leave_the_king_attacked_say_so() {
+        // The rook on e8 checks the king on e1
+        let mut game = Game::from_fen("k3r3/8/8/8/8/8/3P4/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            game.make_move(parse_move("d2d3").unwrap()),
+            Err(MoveError::MustGetOutOfCheck)
+        );
+        assert_eq!(
+            game.make_move(parse_move("d2d5").unwrap()),
+            Err(MoveError::IllegalMove)
+        );
+        assert!(game.make_move(parse_move("e1f1").unwrap()).is_ok());
+    }
+}
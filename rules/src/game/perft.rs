@@ -0,0 +1,120 @@
+// =======================================================
+// Project: GatedChess
+// File: perft.rs
+// Description: Counts move tree nodes to check move generation.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::Game;
+
+impl Game {
+    /// Counts the leaf nodes of the legal move tree `depth` plies deep.
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.get_all_legal_moves(self.current_turn);
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves {
+            self.play_unchecked(&mv);
+            nodes += self.perft(depth - 1);
+            self.unmake_move_unchecked();
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::Game;
+    use crate::game::fen::START_FEN;
+    use crate::gates::GateRules;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    const ENDGAME: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    const PROMOTIONS: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    /// Kiwipete with gates on c1, which stops queenside castling, and f5.
+    const GATED_KIWIPETE: &str =
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1 c1:1,f5:2";
+    const CHECKS: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+    /// Perft with gates turned off, which must match standard chess.
+    fn standard_perft(fen: &str, depth: u32) -> u64 {
+        let mut game = Game::from_fen(fen).unwrap();
+        game.set_gate_rules(GateRules::NONE);
+        game.perft(depth)
+    }
+
+    fn gated_perft(fen: &str, depth: u32) -> u64 {
+        Game::from_fen(fen).unwrap().perft(depth)
+    }
+
+    #[test]
+    fn standard_start_position() {
+        assert_eq!(standard_perft(START_FEN, 1), 20);
+        assert_eq!(standard_perft(START_FEN, 2), 400);
+        assert_eq!(standard_perft(START_FEN, 3), 8902);
+    }
+
+    #[test]
+    fn standard_kiwipete() {
+        assert_eq!(standard_perft(KIWIPETE, 1), 48);
+        assert_eq!(standard_perft(KIWIPETE, 2), 2039);
+    }
+
+    #[test]
+    fn standard_endgame() {
+        assert_eq!(standard_perft(ENDGAME, 1), 14);
+        assert_eq!(standard_perft(ENDGAME, 2), 191);
+        assert_eq!(standard_perft(ENDGAME, 3), 2812);
+    }
+
+    #[test]
+    fn standard_promotions() {
+        assert_eq!(standard_perft(PROMOTIONS, 1), 6);
+        assert_eq!(standard_perft(PROMOTIONS, 2), 264);
+        assert_eq!(standard_perft(PROMOTIONS, 3), 9467);
+    }
+
+    #[test]
+    fn standard_checks() {
+        assert_eq!(standard_perft(CHECKS, 1), 44);
+        assert_eq!(standard_perft(CHECKS, 2), 1486);
+    }
+
+    #[test]
+    fn standard_deep() {
+        assert_eq!(standard_perft(START_FEN, 4), 197281);
+        assert_eq!(standard_perft(KIWIPETE, 3), 97862);
+        assert_eq!(standard_perft(ENDGAME, 4), 43238);
+        assert_eq!(standard_perft(CHECKS, 3), 62379);
+    }
+
+    /// Counts under the gated rules, recorded from this move generator so
+    /// that changes to gate handling show up as a diff.
+    #[test]
+    fn gated_counts() {
+        assert_eq!(gated_perft(START_FEN, 3), 8902);
+        assert_eq!(gated_perft(KIWIPETE, 2), 2036);
+        assert_eq!(gated_perft(ENDGAME, 3), 2796);
+        assert_eq!(gated_perft(PROMOTIONS, 2), 379);
+        assert_eq!(gated_perft(CHECKS, 2), 1483);
+        assert_eq!(gated_perft(GATED_KIWIPETE, 1), 42);
+        assert_eq!(gated_perft(GATED_KIWIPETE, 2), 1780);
+    }
+
+    #[test]
+    fn gated_deep() {
+        assert_eq!(gated_perft(START_FEN, 4), 197221);
+        assert_eq!(gated_perft(KIWIPETE, 3), 97283);
+        assert_eq!(gated_perft(GATED_KIWIPETE, 3), 85132);
+    }
+}
@@ -0,0 +1,115 @@
+// =======================================================
+// Project: GatedChess
+// File: setups.rs
+// Description: Chess960 and curated gated starting positions.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Starting positions besides the standard one. Each comes out as a `Game`
+//! whose `start_fen` says everything about it, so a FEN is all it takes to
+//! hand one to an opponent or save it with the game.
+
+use crate::board::{chess960_back_rank, create_board_with};
+use crate::game::Game;
+use crate::game::moves::castling::CastlingFiles;
+use crate::gates::GateRules;
+
+/// A starting position with gates already standing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GatedStart {
+    pub name: &'static str,
+    pub fen: &'static str,
+}
+
+pub const GATED_STARTS: [GatedStart; 3] = [
+    GatedStart {
+        name: "Four Pillars",
+        fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 c4:#w,f4:#w,c5:#b,f5:#b",
+    },
+    GatedStart {
+        name: "Portal Flanks",
+        fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 \
+              a4:40w@h5,h5:40b@a4,h4:40w@a5,a5:40b@h4",
+    },
+    GatedStart {
+        name: "One-Way Lines",
+        fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 \
+              c3:30w^,f3:30w^,c6:30bv,f6:30bv",
+    },
+];
+
+impl Game {
+    /// Chess960 position `number`, 0 to 959, played by `gate_rules`.
+    pub fn chess960(number: u16, gate_rules: GateRules) -> Game {
+        let back_rank = chess960_back_rank(number);
+        let mut game = Game::new(create_board_with(back_rank), gate_rules);
+        game.castling_files = CastlingFiles::from_back_rank(&back_rank);
+        game.start_fen = game.to_fen();
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Position;
+    use crate::game::fen::START_FEN;
+    use crate::parser::algebraic::parse_move;
+    use crate::pieces::PieceType;
+
+    #[test]
+    fn chess960_numbers_and_fens() {
+        assert_eq!(Game::chess960(518, GateRules::STANDARD).to_fen(), START_FEN);
+        let game = Game::chess960(0, GateRules::STANDARD);
+        assert_eq!(
+            game.to_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1"
+        );
+        assert_eq!(Game::from_fen(&game.to_fen()).unwrap().castling_files, game.castling_files);
+
+        for start in GATED_STARTS {
+            assert!(Game::from_fen(start.fen).is_ok(), "{}", start.name);
+        }
+    }
+
+    #[test]
+    fn chess960_perft() {
+        // Known counts for positions from the usual Chess960 perft suite
+        for (fen, counts) in [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12189],
+            ),
+            (
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                [21, 807, 18002],
+            ),
+        ] {
+            let mut game = Game::from_fen(fen).unwrap();
+            game.set_gate_rules(GateRules::NONE);
+            for (depth, count) in counts.into_iter().enumerate() {
+                assert_eq!(game.perft(depth as u32 + 1), count, "{} {}", fen, depth + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn king_and_rook_can_swap_squares() {
+        // King on d1 and rook on c1 trade places when castling queenside
+        let fen = "4k3/p7/8/8/8/8/P7/2RK4 w C - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+        let played = game.make_move(parse_move("d1c1").unwrap()).unwrap();
+        assert!(played.castling.is_some());
+        assert!(played.captured.is_none());
+        assert_eq!(played.landing(), Position::from_algebraic("c1").unwrap());
+        assert_eq!(game.board[0][2].piece.unwrap().kind, PieceType::King);
+        assert_eq!(game.board[0][3].piece.unwrap().kind, PieceType::Rook);
+        assert!(!game.castling_rights.white_queenside);
+
+        game.unmake_move();
+        assert_eq!(game.to_fen(), fen);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::board::Square;
+use crate::game::Position;
+use crate::game::events::GameEvent;
+use crate::pieces::piece_fsm::PieceFSM;
+use crate::pieces::piece_fsm::*;
+use crate::pieces::{Piece, PieceId, PieceType};
+use std::collections::HashMap;
+
+pub struct GameStateManager {
+    pub piece_fsms: HashMap<PieceId, Box<dyn PieceFSM>>,
+    pub piece_positions: HashMap<PieceId, Position>,
+    /// The piece the player has picked up, if any.
+    selected: Option<PieceId>,
+    /// The piece that moved last, which any capture in the same move is by.
+    last_mover: Option<PieceId>,
+}
+
+impl GameStateManager {
+    pub fn new() -> Self {
+        Self {
+            piece_fsms: HashMap::new(),
+            piece_positions: HashMap::new(),
+            selected: None,
+            last_mover: None,
+        }
+    }
+
+    pub fn register_all_pieces(
+        &mut self,
+        board: &mut [[Square; crate::config::BOARD_SIZE]; crate::config::BOARD_SIZE],
+    ) {
+        for (row, squares) in board.iter().enumerate() {
+            for (col, square) in squares.iter().enumerate() {
+                if let Some(piece) = square.piece {
+                    self.register_piece(piece, Position { row, col });
+                }
+            }
+        }
+    }
+
+    pub fn register_piece(&mut self, piece: crate::pieces::Piece, pos: Position) {
+        let fsm: Box<dyn PieceFSM> = match piece.kind {
+            crate::pieces::PieceType::Pawn => Box::new(PawnFSM::new(piece)),
+            crate::pieces::PieceType::Rook => Box::new(RookFSM::new(piece)),
+            crate::pieces::PieceType::Knight => Box::new(KnightFSM::new(piece)),
+            crate::pieces::PieceType::Bishop => Box::new(BishopFSM::new(piece)),
+            crate::pieces::PieceType::Queen => Box::new(QueenFSM::new(piece)),
+            crate::pieces::PieceType::King => Box::new(KingFSM::new(piece)),
+        };
+
+        self.piece_fsms.insert(piece.id, fsm);
+        self.piece_positions.insert(piece.id, pos);
+    }
+
+    pub fn update_all_fsm(
+        &mut self,
+        board: &mut [[Square; crate::config::BOARD_SIZE]; crate::config::BOARD_SIZE],
+    ) {
+        let mut updates: Vec<(Position, Piece)> = Vec::new();
+
+        for (piece_id, fsm) in self.piece_fsms.iter_mut() {
+            if let Some(&pos) = self.piece_positions.get(piece_id) {
+                let updated_piece = *fsm.piece();
+                updates.push((pos, updated_piece));
+            }
+        }
+
+        for (pos, piece) in updates {
+            if board[pos.row][pos.col].piece.is_some() {
+                board[pos.row][pos.col].piece = Some(piece);
+            }
+        }
+    }
+
+    pub fn fsm(&self, id: PieceId) -> Option<&dyn PieceFSM> {
+        self.piece_fsms.get(&id).map(|fsm| fsm.as_ref())
+    }
+
+    /// Moves every piece's timed state on by `dt` seconds. `duration` says
+    /// how long a kind of piece holds a state, or `None` for states that
+    /// last until the game moves on.
+    pub fn update(&mut self, dt: f32, duration: impl Fn(PieceType, PieceState) -> Option<f32>) {
+        for fsm in self.piece_fsms.values_mut() {
+            let limit = duration(fsm.piece().kind, fsm.state());
+            fsm.update_state(dt, limit);
+        }
+    }
+
+    /// Marks `id` as the piece the player is holding, letting go of the
+    /// one held before. Pieces busy with something else stay as they are.
+    pub fn select(&mut self, id: Option<PieceId>) {
+        if self.selected == id {
+            return;
+        }
+        if let Some(old) = self.selected.and_then(|old| self.piece_fsms.get_mut(&old))
+            && old.state() == PieceState::Selected
+        {
+            old.set_state(PieceState::Idle);
+        }
+        if let Some(new) = id.and_then(|new| self.piece_fsms.get_mut(&new))
+            && new.state() == PieceState::Idle
+        {
+            new.set_state(PieceState::Selected);
+        }
+        self.selected = id;
+    }
+
+    /// Feeds the events of one move to the pieces they concern. A move ends
+    /// any check and lets go of the selected piece.
+    pub fn handle_move(&mut self, events: &[GameEvent]) {
+        for fsm in self.piece_fsms.values_mut() {
+            if matches!(fsm.state(), PieceState::InCheck | PieceState::Selected) {
+                fsm.set_state(PieceState::Idle);
+            }
+        }
+        self.selected = None;
+        self.last_mover = None;
+        for event in events {
+            self.handle_event(event);
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::PieceMoved { piece, .. } => {
+                self.set_state(piece.id, PieceState::Moving);
+                self.last_mover = Some(piece.id);
+            }
+            GameEvent::PieceCaptured { .. } => {
+                if let Some(id) = self.last_mover {
+                    self.set_state(id, PieceState::Capturing);
+                }
+            }
+            GameEvent::PiecePromoted { piece, .. } => self.set_state(piece.id, PieceState::Promoted),
+            GameEvent::Check { king, .. } => {
+                let id = self
+                    .piece_positions
+                    .iter()
+                    .find(|(_, pos)| *pos == king)
+                    .map(|(&id, _)| id);
+                if let Some(id) = id {
+                    self.set_state(id, PieceState::InCheck);
+                }
+            }
+            GameEvent::MoveTakenBack(_) => {
+                for fsm in self.piece_fsms.values_mut() {
+                    fsm.set_state(PieceState::Idle);
+                }
+                self.selected = None;
+                self.last_mover = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn set_state(&mut self, id: PieceId, state: PieceState) {
+        if let Some(fsm) = self.piece_fsms.get_mut(&id) {
+            fsm.set_state(state);
+        }
+    }
+}
+
+impl Default for GameStateManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use crate::parser::algebraic::parse_move;
+
+    // Capturing and moving settle after a second, the rest wait for a move
+    fn duration(_: PieceType, state: PieceState) -> Option<f32> {
+        matches!(state, PieceState::Moving | PieceState::Capturing).then_some(1.0)
+    }
+
+    fn state_at(game: &Game, square: &str) -> PieceState {
+        let pos = Position::from_algebraic(square).unwrap();
+        let piece = game.board[pos.row][pos.col].piece.unwrap();
+        game.state_manager.fsm(piece.id).unwrap().state()
+    }
+
+    #[test]
+    fn captures_play_out_and_settle_back_to_idle() {
+        let mut game = Game::from_fen("4k3/p7/8/3p4/4P3/8/P7/4K3 w - - 0 1").unwrap();
+        let victim = game.board[4][3].piece.unwrap().id;
+        game.state_manager.select(Some(game.board[3][4].piece.unwrap().id));
+        assert_eq!(state_at(&game, "e4"), PieceState::Selected);
+
+        game.make_move(parse_move("e4d5").unwrap()).unwrap();
+        assert_eq!(state_at(&game, "d5"), PieceState::Capturing);
+        assert!(game.state_manager.fsm(victim).is_none());
+
+        game.state_manager.update(0.5, duration);
+        assert_eq!(state_at(&game, "d5"), PieceState::Capturing);
+        game.state_manager.update(0.5, duration);
+        assert_eq!(state_at(&game, "d5"), PieceState::Idle);
+
+        // The captured pawn comes back idle
+        game.unmake_move();
+        assert_eq!(game.state_manager.fsm(victim).unwrap().state(), PieceState::Idle);
+    }
+
+    #[test]
+    fn checks_and_promotions_reach_the_right_pieces() {
+        let mut game = Game::from_fen("8/P3k3/8/8/8/8/p7/4K3 w - - 0 1").unwrap();
+        game.make_move(parse_move("a7a8q").unwrap()).unwrap();
+        assert_eq!(state_at(&game, "a8"), PieceState::Promoted);
+
+        game.make_move(parse_move("e7d6").unwrap()).unwrap();
+        game.make_move(parse_move("a8d8").unwrap()).unwrap();
+        assert_eq!(state_at(&game, "d6"), PieceState::InCheck);
+        assert_eq!(state_at(&game, "d8"), PieceState::Moving);
+
+        // Only the next move ends the check, not time
+        game.state_manager.update(10.0, duration);
+        assert_eq!(state_at(&game, "d6"), PieceState::InCheck);
+        game.make_move(parse_move("d6e5").unwrap()).unwrap();
+        assert_eq!(state_at(&game, "e5"), PieceState::Moving);
+        game.state_manager.update(1.0, duration);
+        assert_eq!(state_at(&game, "e5"), PieceState::Idle);
+    }
+}
@@ -0,0 +1,240 @@
+// =======================================================
+// Project: GatedChess
+// File: undo.rs
+// Description: Undo stack for reversible make/unmake.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::config::BOARD_SIZE;
+use crate::game::events::GameEvent;
+use crate::game::moves::castling::CastlingRights;
+use crate::game::state_machine::GameStateManager;
+use crate::game::{Game, GameResult, Move, Position};
+use crate::gates::GateType;
+use crate::pieces::Color;
+use crate::pieces::piece_fsm::{PieceFSM, PieceState};
+
+/// Everything a move changes that can't be read back from its `Move` record.
+pub struct Undo {
+    pub mv: Move,
+    pub turn: Color,
+    pub result: GameResult,
+    pub castling_rights: CastlingRights,
+    pub en_passant: Option<Position>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+    pub hash: u64,
+    pub gate_stock: [u8; 2],
+    /// Gates as they were before the move, durations included.
+    pub gates: [[Option<GateType>; BOARD_SIZE]; BOARD_SIZE],
+    /// FSMs taken off the board by the move, with the square they left.
+    pub retired: Vec<(Box<dyn PieceFSM>, Position)>,
+}
+
+impl Game {
+    /// Takes back the last move, restoring the board, gates, turn, result and
+    /// piece FSMs exactly. The move can be replayed with `redo_move`.
+    pub fn unmake_move(&mut self) -> Option<Move> {
+        // The promoted piece only exists on the board, so note it first
+        let promoted = self
+            .undo_stack
+            .last()
+            .filter(|undo| undo.mv.promotion.is_some())
+            .and_then(|undo| self.board[undo.mv.to.row][undo.mv.to.col].piece);
+
+        let undo = self.unmake_move_unchecked()?;
+        let mv = undo.mv;
+
+        if let Some(p) = promoted {
+            self.state_manager.piece_fsms.remove(&p.id);
+            self.state_manager.piece_positions.remove(&p.id);
+        }
+        if let Some(p) = mv.piece {
+            self.state_manager.piece_positions.insert(p.id, mv.from);
+        }
+        if let Some(side) = mv.castling {
+            let rook_from = Position {
+                row: mv.from.row,
+                col: self.castling_files.rook(side),
+            };
+            if let Some(rook) = self.board[rook_from.row][rook_from.col].piece {
+                self.state_manager.piece_positions.insert(rook.id, rook_from);
+            }
+        }
+        for (mut fsm, pos) in undo.retired {
+            let id = fsm.piece().id;
+            fsm.set_state(PieceState::Idle);
+            self.state_manager.piece_fsms.insert(id, fsm);
+            self.state_manager.piece_positions.insert(id, pos);
+        }
+
+        self.state_manager.update_all_fsm(&mut self.board);
+
+        let taken_back = GameEvent::MoveTakenBack(mv.clone());
+        self.state_manager.handle_event(&taken_back);
+        let color = self.current_turn;
+        if let Some(king) = self.bitboards.king_square(color)
+            && self.is_king_in_check(color)
+        {
+            self.state_manager.handle_event(&GameEvent::Check { color, king });
+        }
+        self.events.push(taken_back);
+        self.redo_stack.push(mv.clone());
+        Some(mv)
+    }
+
+    /// Replays the most recently undone move. A move that fails to replay
+    /// stays queued.
+    pub fn redo_move(&mut self) -> Option<Move> {
+        let mv = self.redo_stack.pop()?;
+
+        // make_move clears the redo stack, so keep the rest of it aside
+        let pending = std::mem::take(&mut self.redo_stack);
+        let played = self.make_move(mv.clone());
+        self.redo_stack = pending;
+
+        // An undone move always replays; if it somehow does not, keep it
+        // queued rather than losing the rest of the line
+        debug_assert!(played.is_ok(), "redo of {:?} failed", mv);
+        if played.is_err() {
+            self.redo_stack.push(mv);
+        }
+        played.ok()
+    }
+
+    /// Moves played since the starting position, oldest first.
+    pub fn move_list(&self) -> Vec<Move> {
+        self.undo_stack.iter().map(|undo| undo.mv.clone()).collect()
+    }
+
+    /// The move played most recently, as `make_move` recorded it.
+    pub fn last_move(&self) -> Option<&Move> {
+        self.undo_stack.last().map(|undo| &undo.mv)
+    }
+
+    pub fn start_fen(&self) -> &str {
+        &self.start_fen
+    }
+
+    /// The move `redo_move` would replay.
+    pub fn next_redo(&self) -> Option<&Move> {
+        self.redo_stack.last()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Reverts the last `make_move_unchecked` or `place_gate_unchecked` on
+    /// the board and rules state.
+    /// Piece FSMs are left alone.
+    pub fn unmake_move_unchecked(&mut self) -> Option<Undo> {
+        let undo = self.undo_stack.pop()?;
+        let mv = &undo.mv;
+        let (from, to) = (mv.from, mv.to);
+
+        if let Some(side) = mv.castling {
+            // Lift king and rook before putting either back, as they may
+            // have landed on each other's starting squares
+            let rook_from = Position {
+                row: from.row,
+                col: self.castling_files.rook(side),
+            };
+            let rook_to = Position {
+                row: from.row,
+                col: side.rook_target_col(),
+            };
+            let rook = self.board[rook_to.row][rook_to.col].piece;
+            self.set_piece(rook_to, None);
+            self.set_piece(mv.landing(), None);
+            self.set_piece(rook_from, rook);
+            self.set_piece(from, mv.piece);
+        } else {
+            // Put the original piece back, which also undoes a promotion
+            self.set_piece(to, None);
+            self.set_piece(from, mv.piece);
+
+            // An en passant capture took the pawn beside the destination
+            let captured_pos = if mv.en_passant {
+                Position {
+                    row: from.row,
+                    col: to.col,
+                }
+            } else {
+                to
+            };
+            self.set_piece(captured_pos, mv.captured);
+        }
+
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                if self.board[row][col].gate != undo.gates[row][col] {
+                    self.set_gate(Position { row, col }, undo.gates[row][col]);
+                }
+            }
+        }
+
+        self.current_turn = undo.turn;
+        self.result = undo.result.clone();
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.hash = undo.hash;
+        self.gate_stock = undo.gate_stock;
+        self.position_history.pop();
+
+        Some(undo)
+    }
+
+    /// Saves the state a move is about to change.
+    pub(crate) fn push_undo(&mut self, mv: &Move) {
+        let gates = self.board.map(|squares| squares.map(|square| square.gate));
+
+        self.undo_stack.push(Undo {
+            mv: mv.clone(),
+            turn: self.current_turn,
+            result: self.result.clone(),
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            hash: self.hash,
+            gate_stock: self.gate_stock,
+            gates,
+            retired: Vec::new(),
+        });
+    }
+
+    /// Copy of the position without piece FSMs or history, for trying moves
+    /// with make/unmake without touching the real game.
+    pub fn scratch_copy(&self) -> Game {
+        Game {
+            board: self.board,
+            bitboards: self.bitboards,
+            state_manager: GameStateManager::new(),
+            current_turn: self.current_turn,
+            result: self.result.clone(),
+            castling_rights: self.castling_rights,
+            castling_files: self.castling_files,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            start_fen: self.start_fen.clone(),
+            hash: self.hash,
+            position_history: self.position_history.clone(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            gate_rules: self.gate_rules,
+            gate_stock: self.gate_stock,
+            events: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// =======================================================
+// Project: GatedChess
+// File: zobrist.rs
+// Description: Zobrist hashing of positions, gates included.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Every piece on a square, every gate on a square with its time left, the
+//! side to move, each castling right, the en passant file and each side's
+//! stock of gates to place has its own random key. A position's hash is the
+//! XOR of the keys that apply to it, so `Game` keeps it up to date by XORing
+//! keys in and out as squares change.
+
+use crate::config::BOARD_SIZE;
+use crate::game::bitboard::{SQUARES, square_index};
+use crate::game::moves::castling::CastlingRights;
+use crate::game::moves::generation::get_piece_moves;
+use crate::game::{Game, Position};
+use crate::gates::GateType;
+use crate::pieces::{Color, Piece, PieceType};
+
+// Keys are numbered and scrambled, so they stay the same from run to run
+const SIDE_INDEX: u64 = 768;
+const CASTLING_INDEX: u64 = 769;
+const EN_PASSANT_INDEX: u64 = 773;
+const GATE_INDEX: u64 = 1024;
+// Past every gate key
+const STOCK_INDEX: u64 = 1 << 32;
+
+static PIECE_KEYS: [[[u64; SQUARES]; 6]; 2] = piece_table();
+
+/// SplitMix64, a fast scrambler whose outputs are spread well enough to
+/// serve as keys.
+const fn splitmix64(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const fn piece_table() -> [[[u64; SQUARES]; 6]; 2] {
+    let mut table = [[[0; SQUARES]; 6]; 2];
+    let mut index = 0;
+    while index < 2 * 6 * SQUARES {
+        table[index / (6 * SQUARES)][index / SQUARES % 6][index % SQUARES] =
+            splitmix64(index as u64);
+        index += 1;
+    }
+    table
+}
+
+pub fn piece_key(pos: Position, piece: Piece) -> u64 {
+    PIECE_KEYS[piece.color as usize][piece.kind as usize][square_index(pos)]
+}
+
+/// Gates with different time left hash differently, since they free the
+/// square on different turns, and so do gates raised by different sides or
+/// of different kinds.
+pub fn gate_key(pos: Position, gate: GateType) -> u64 {
+    // Standard, wall, one of four one-way headings, or a portal to any square
+    let kind = match gate {
+        GateType::Standard { .. } => 0,
+        GateType::Wall { .. } => 1,
+        GateType::OneWay { toward, .. } => 2 + toward as usize,
+        GateType::Portal { exit, .. } => 6 + square_index(exit),
+    };
+    let slot = (square_index(pos) * 2 + gate.owner() as usize) * (6 + SQUARES) + kind;
+    let duration = gate.duration().unwrap_or(0);
+    splitmix64(GATE_INDEX + (slot * 256) as u64 + duration as u64)
+}
+
+/// Keys for everything besides the squares: side to move, castling rights,
+/// the en passant file and the gates each side has left to place.
+pub fn rules_key(
+    turn: Color,
+    rights: CastlingRights,
+    en_passant: Option<Position>,
+    gate_stock: [u8; 2],
+) -> u64 {
+    let mut key = 0;
+    if turn == Color::Black {
+        key ^= splitmix64(SIDE_INDEX);
+    }
+    for (i, has) in [
+        rights.white_kingside,
+        rights.white_queenside,
+        rights.black_kingside,
+        rights.black_queenside,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if has {
+            key ^= splitmix64(CASTLING_INDEX + i as u64);
+        }
+    }
+    if let Some(pos) = en_passant {
+        key ^= splitmix64(EN_PASSANT_INDEX + pos.col as u64);
+    }
+    for (side, stock) in gate_stock.into_iter().enumerate() {
+        if stock > 0 {
+            key ^= splitmix64(STOCK_INDEX + (side * 256) as u64 + stock as u64);
+        }
+    }
+    key
+}
+
+impl Game {
+    /// Identifies the position for repetition and lookup tables. Two
+    /// positions only match if the same gates stand on the same squares with
+    /// the same time left, as well as the usual pieces, side to move,
+    /// castling and en passant.
+    pub fn position_key(&self) -> u64 {
+        self.hash
+    }
+
+    /// Hash of the current position built from scratch. `hash` is kept equal
+    /// to this as moves are made and taken back.
+    pub(crate) fn compute_hash(&self) -> u64 {
+        let mut hash = self.current_rules_key();
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                let pos = Position { row, col };
+                let square = &self.board[row][col];
+                if let Some(piece) = square.piece {
+                    hash ^= piece_key(pos, piece);
+                }
+                if let Some(gate) = square.gate {
+                    hash ^= gate_key(pos, gate);
+                }
+            }
+        }
+        hash
+    }
+
+    pub(crate) fn current_rules_key(&self) -> u64 {
+        rules_key(
+            self.current_turn,
+            self.castling_rights,
+            self.capturable_en_passant(),
+            self.gate_stock,
+        )
+    }
+
+    /// The en passant square, but only if the side to move can legally take
+    /// on it. A double push nothing can answer leaves the same choice of
+    /// moves as any other push, so it must not keep repetitions apart.
+    fn capturable_en_passant(&self) -> Option<Position> {
+        let target = self.en_passant?;
+        let color = self.current_turn;
+        // Capturing pawns stand level with the pawn that was pushed
+        let row = match color {
+            Color::White => target.row.checked_sub(1)?,
+            Color::Black => target.row + 1,
+        };
+        let capturable = [target.col.checked_sub(1), Some(target.col + 1)]
+            .into_iter()
+            .flatten()
+            .filter(|&col| col < BOARD_SIZE && row < BOARD_SIZE)
+            .map(|col| Position { row, col })
+            .any(|from| {
+                matches!(
+                    self.board[from.row][from.col].piece,
+                    Some(p) if p.kind == PieceType::Pawn && p.color == color
+                ) && get_piece_moves(self, from, color).contains(&target)
+                    && !self.move_exposes_king(from, target, color)
+            });
+        capturable.then_some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::Game;
+    use crate::game::fen::START_FEN;
+    use crate::parser::algebraic::parse_move;
+
+    fn play(game: &mut Game, moves: &[&str]) {
+        for text in moves {
+            game.make_move(parse_move(text).unwrap()).unwrap();
+            assert_eq!(game.position_key(), game.compute_hash(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn incremental_hash_matches_scratch() {
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        let start = game.position_key();
+        play(&mut game, &["e2e4", "d7d5", "e4d5", "c8g4", "f1b5", "b8c6"]);
+        while game.unmake_move().is_some() {
+            assert_eq!(game.position_key(), game.compute_hash());
+        }
+        assert_eq!(game.position_key(), start);
+    }
+
+    #[test]
+    fn transpositions_share_a_hash() {
+        let mut a = Game::from_fen(START_FEN).unwrap();
+        let mut b = Game::from_fen(START_FEN).unwrap();
+        play(&mut a, &["g1f3", "g8f6", "b1c3", "b8c6"]);
+        play(&mut b, &["b1c3", "b8c6", "g1f3", "g8f6"]);
+        assert_eq!(a.position_key(), b.position_key());
+    }
+
+    #[test]
+    fn gate_time_left_changes_the_hash() {
+        let board = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RN1QKBNR w KQkq - 0 1";
+        let fresh = Game::from_fen(&format!("{} c1:2", board)).unwrap();
+        let aging = Game::from_fen(&format!("{} c1:1", board)).unwrap();
+        assert_ne!(fresh.position_key(), aging.position_key());
+    }
+
+    #[test]
+    fn en_passant_only_hashed_when_capturable() {
+        // No black pawn can take on e3, so the push repeats once the
+        // knights have gone out and back
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        play(&mut game, &["e2e4"]);
+        let pushed = game.position_key();
+        play(&mut game, &["g8f6", "g1f3", "f6g8", "f3g1"]);
+        assert_eq!(game.position_key(), pushed);
+        assert_eq!(game.repetition_count(), 2);
+
+        // A black pawn on d4 can take on e3, which the later position can't
+        let board = "rnbqkbnr/ppp1pppp/8/8/3p4/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let mut game = Game::from_fen(board).unwrap();
+        play(&mut game, &["e2e4"]);
+        let pushed = game.position_key();
+        play(&mut game, &["g8f6", "g1f3", "f6g8", "f3g1"]);
+        assert_ne!(game.position_key(), pushed);
+        assert_eq!(game.repetition_count(), 1);
+    }
+}
@@ -0,0 +1,117 @@
+// =======================================================
+// Project: GatedChess
+// File: gate_type.rs
+// Description: Defines various gate types used in-game.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::Position;
+use crate::pieces::Color;
+
+/// Which way a one-way gate lets pieces through. North is towards Black's
+/// side of the board, East towards the h-file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Arrow used for this direction in FEN, e.g. the `^` in `d4:2w^`.
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|dir| dir.to_char() == c)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GateType {
+    /// Stands for `duration` more turns. `owner` is the side whose move made
+    /// it, which matters when the game's `GateRules` let owners through.
+    Standard { duration: u8, owner: Color },
+    /// Never expires.
+    Wall { owner: Color },
+    /// Lets pieces in or through only while their move heads `toward`, or
+    /// runs square across it. Any move with a step the other way is blocked.
+    OneWay {
+        duration: u8,
+        owner: Color,
+        toward: Direction,
+    },
+    /// One end of a pair. A slider that runs into it carries on in the same
+    /// direction from `exit`, the other end. Nothing can stop on a portal.
+    Portal {
+        duration: u8,
+        owner: Color,
+        exit: Position,
+    },
+}
+
+impl GateType {
+    pub fn owner(self) -> Color {
+        match self {
+            GateType::Standard { owner, .. }
+            | GateType::Wall { owner }
+            | GateType::OneWay { owner, .. }
+            | GateType::Portal { owner, .. } => owner,
+        }
+    }
+
+    /// Turns left, or `None` for a gate that never expires.
+    pub fn duration(self) -> Option<u8> {
+        match self {
+            GateType::Standard { duration, .. }
+            | GateType::OneWay { duration, .. }
+            | GateType::Portal { duration, .. } => Some(duration),
+            GateType::Wall { .. } => None,
+        }
+    }
+
+    /// The gate a turn later, or `None` once its time is up.
+    pub fn aged(self) -> Option<GateType> {
+        let left = match self.duration() {
+            Some(duration) => duration.saturating_sub(1),
+            None => return Some(self),
+        };
+        if left == 0 {
+            return None;
+        }
+        Some(match self {
+            GateType::Standard { owner, .. } => GateType::Standard {
+                duration: left,
+                owner,
+            },
+            GateType::OneWay { owner, toward, .. } => GateType::OneWay {
+                duration: left,
+                owner,
+                toward,
+            },
+            GateType::Portal { owner, exit, .. } => GateType::Portal {
+                duration: left,
+                owner,
+                exit,
+            },
+            GateType::Wall { .. } => self,
+        })
+    }
+}
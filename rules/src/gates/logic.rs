@@ -0,0 +1,36 @@
+// =======================================================
+// Project: GatedChess
+// File: mod.rs
+// Description: Defines gate creation and updates.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::bitboard::squares;
+use crate::game::{Game, Position};
+
+/// Ages every gate by one ply, as the ply that `game` is finishing ends,
+/// and returns the squares whose gate came down. A gate of duration `n`
+/// therefore stands through the ply that raised it and the `n - 1` plies
+/// after. Walls never age.
+pub(crate) fn age_gates(game: &mut Game) -> Vec<Position> {
+    let mut expired = Vec::new();
+
+    for pos in squares(game.bitboards.gates) {
+        // Age through `set_gate` so the position hash follows. Walls
+        // come back unchanged and are left alone.
+        if let Some(gate) = game.board[pos.row][pos.col].gate {
+            let aged = gate.aged();
+            if aged != Some(gate) {
+                game.set_gate(pos, aged);
+            }
+            if aged.is_none() {
+                expired.push(pos);
+            }
+        }
+    }
+
+    expired
+}
@@ -4,12 +4,13 @@
 // Description: Defines gate creation and updates.
 // Author: Seamus Daniello
 // Created: 2025-11-07
-// Last Modified: 2025-11-07
+// Last Modified: 2026-10-18
 // License: MIT
 // =======================================================
 
 pub mod gate_type;
 pub mod logic;
+pub mod rules;
 
-pub use gate_type::GateType;
-pub use logic::{update_gate_animation, update_gates};
+pub use gate_type::{Direction, GateType};
+pub use rules::GateRules;
@@ -0,0 +1,273 @@
+// =======================================================
+// Project: GatedChess
+// File: rules.rs
+// Description: Configurable gate behaviour for a game.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::Position;
+use crate::game::bitboard::{Bitboard, between, square_bit};
+use crate::pieces::PieceType;
+
+/// How gates behave in one game. Chosen before the game starts and fixed
+/// for its length, so both players of an online game must agree on it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GateRules {
+    /// Piece kinds whose moves leave gates, indexed by `PieceType as usize`.
+    pub spawners: [bool; 6],
+    /// Plies a new gate stands for, counting the one that raises it, so the
+    /// standard 2 keeps it up through the opponent's reply.
+    pub duration: u8,
+    /// Gates on the empty squares a piece passes over.
+    pub on_path: bool,
+    /// A gate on the square the piece left.
+    pub on_origin: bool,
+    /// A gate under the piece on the square it lands on.
+    pub on_destination: bool,
+    /// When false, a gate only stops the opponent of the side that made it.
+    pub blocks_owner: bool,
+    /// Gates each side may place on an empty square instead of moving. Zero
+    /// turns placement off.
+    pub placement_stock: u8,
+}
+
+impl GateRules {
+    /// Rooks and bishops leave two-turn gates along their path, blocking
+    /// everyone.
+    pub const STANDARD: GateRules = GateRules {
+        spawners: [false, true, false, true, false, false],
+        duration: 2,
+        on_path: true,
+        on_origin: false,
+        on_destination: false,
+        blocks_owner: true,
+        placement_stock: 0,
+    };
+
+    /// No piece leaves gates, which is standard chess.
+    pub const NONE: GateRules = GateRules {
+        spawners: [false; 6],
+        ..GateRules::STANDARD
+    };
+
+    /// Longest `duration` the menus offer.
+    pub const MAX_DURATION: u8 = 9;
+
+    /// Largest `placement_stock` the menus offer.
+    pub const MAX_STOCK: u8 = 9;
+
+    pub fn spawns(&self, kind: PieceType) -> bool {
+        self.spawners[kind as usize]
+    }
+
+    /// Writes the rules as `<spawning pieces> <duration> <placements>
+    /// <blocks owner> <stock>`, such as `rb 2 p 1 0`. Placements are
+    /// `p`ath, `o`rigin and `d`estination, and `-` stands for an empty list.
+    pub fn to_notation(&self) -> String {
+        let list = |letters: String| if letters.is_empty() { "-".to_string() } else { letters };
+        let spawners: String = PieceType::ALL
+            .iter()
+            .filter(|kind| self.spawns(**kind))
+            .map(|kind| kind.to_char())
+            .collect();
+        let placements: String = [
+            (self.on_path, 'p'),
+            (self.on_origin, 'o'),
+            (self.on_destination, 'd'),
+        ]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, letter)| *letter)
+        .collect();
+        format!(
+            "{} {} {} {} {}",
+            list(spawners),
+            self.duration,
+            list(placements),
+            self.blocks_owner as u8,
+            self.placement_stock
+        )
+    }
+
+    /// Reads rules written by `to_notation`, refusing durations and stocks
+    /// the menus don't offer.
+    pub fn from_notation(text: &str) -> Option<GateRules> {
+        let mut parts = text.split_whitespace();
+
+        let mut rules = GateRules::NONE;
+        let spawners = parts.next()?;
+        if spawners != "-" {
+            for letter in spawners.chars() {
+                rules.spawners[PieceType::from_char(letter)? as usize] = true;
+            }
+        }
+        rules.duration = parts.next()?.parse().ok()?;
+        if !(1..=GateRules::MAX_DURATION).contains(&rules.duration) {
+            return None;
+        }
+        let placements = parts.next()?;
+        rules.on_path = placements.contains('p');
+        rules.on_origin = placements.contains('o');
+        rules.on_destination = placements.contains('d');
+        rules.blocks_owner = match parts.next()? {
+            "1" => true,
+            "0" => false,
+            _ => return None,
+        };
+        rules.placement_stock = parts.next()?.parse().ok()?;
+        if rules.placement_stock > GateRules::MAX_STOCK || parts.next().is_some() {
+            return None;
+        }
+
+        Some(rules)
+    }
+
+    /// Squares that get a gate when a `kind` moves from `from` to `to`.
+    /// `occupied` is every piece after the move; of the squares left
+    /// behind, only empty ones get a gate.
+    pub fn gate_squares(
+        &self,
+        kind: PieceType,
+        from: Position,
+        to: Position,
+        occupied: Bitboard,
+    ) -> Bitboard {
+        if !self.spawns(kind) {
+            return 0;
+        }
+        let mut squares = 0;
+        if self.on_path {
+            squares |= between(from, to);
+        }
+        if self.on_origin {
+            squares |= square_bit(from);
+        }
+        squares &= !occupied;
+        if self.on_destination {
+            squares |= square_bit(to);
+        }
+        squares
+    }
+}
+
+impl Default for GateRules {
+    fn default() -> Self {
+        GateRules::STANDARD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::fen::START_FEN;
+    use crate::game::{Game, MoveError};
+    use crate::parser::algebraic::parse_move;
+    use crate::pieces::Color;
+
+    fn play(game: &mut Game, text: &str) {
+        game.make_move(parse_move(text).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn knights_can_leave_gates_on_origin_and_destination() {
+        let mut game = Game::from_fen("4k3/p7/8/8/8/8/8/1N2K3 w - - 0 1").unwrap();
+        game.set_gate_rules(GateRules {
+            spawners: [false, false, true, false, false, false],
+            duration: 3,
+            on_path: false,
+            on_origin: true,
+            on_destination: true,
+            blocks_owner: true,
+            placement_stock: 0,
+        });
+        play(&mut game, "b1c3");
+        assert_eq!(game.to_fen(), "4k3/p7/8/8/8/2N5/8/4K3 b - - 1 1 b1:2w,c3:2w");
+    }
+
+    #[test]
+    fn gates_can_let_their_owner_through() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1 a4:2w";
+        let mut game = Game::from_fen(fen).unwrap();
+        assert!(game.make_move(parse_move("a1a8").unwrap()).is_err());
+
+        game.set_gate_rules(GateRules {
+            blocks_owner: false,
+            ..GateRules::STANDARD
+        });
+        play(&mut game, "a1a8");
+        assert!(game.is_king_in_check(Color::Black));
+    }
+
+    #[test]
+    fn gates_age_every_ply_and_come_back_on_undo() {
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        for text in ["e2e3", "e7e6", "f1b5"] {
+            play(&mut game, text);
+        }
+        let gated = "rnbqkbnr/pppp1ppp/4p3/1B6/8/4P3/PPPP1PPP/RNBQK1NR b KQkq - 1 2 e2:1w,d3:1w,c4:1w";
+        assert_eq!(game.to_fen(), gated);
+
+        let played = game.make_move(parse_move("a7a6").unwrap()).unwrap();
+        assert_eq!(played.gates_expired.len(), 3);
+        assert_eq!(game.bitboards().gates, 0);
+        game.unmake_move();
+        assert_eq!(game.to_fen(), gated);
+    }
+
+    #[test]
+    fn expiring_gates_do_not_cover_the_king() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/r3K2R w - - 0 1 c1:1b").unwrap();
+        assert!(!game.is_king_in_check(Color::White));
+        let moves: Vec<String> = game
+            .get_all_legal_moves(Color::White)
+            .iter()
+            .map(|mv| mv.to.to_algebraic())
+            .collect();
+        assert!(!moves.contains(&"h2".to_string()));
+        assert!(moves.contains(&"e2".to_string()));
+    }
+
+    #[test]
+    fn placed_gates_can_block_a_check() {
+        let mut game = Game::from_fen("k3r3/8/8/8/8/8/8/4K3 w - - 0 1 - 1/1").unwrap();
+        let blocks: Vec<String> = game
+            .gate_placements(Color::White)
+            .iter()
+            .map(|pos| pos.to_algebraic())
+            .collect();
+        assert_eq!(blocks, ["e2", "e3", "e4", "e5", "e6", "e7"]);
+        assert_eq!(
+            game.make_move(parse_move("G@a4").unwrap()),
+            Err(MoveError::MustGetOutOfCheck)
+        );
+
+        play(&mut game, "G@e4");
+        assert!(!game.is_king_in_check(Color::White));
+        assert_eq!(game.to_fen(), "k3r3/8/8/8/8/8/8/4K3 b - - 1 1 e4:1w 0/1");
+    }
+
+    #[test]
+    fn placement_stock_runs_out_and_comes_back_on_undo() {
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        game.set_gate_rules(GateRules {
+            placement_stock: 1,
+            ..GateRules::STANDARD
+        });
+        play(&mut game, "G@e5");
+        play(&mut game, "d7d6");
+        assert_eq!(game.gate_stock(Color::White), 0);
+        assert_eq!(
+            game.make_move(parse_move("G@d4").unwrap()),
+            Err(MoveError::NoGatesLeft)
+        );
+
+        game.unmake_move();
+        game.unmake_move();
+        assert_eq!(game.gate_stock(Color::White), 1);
+        assert_eq!(game.position_key(), game.compute_hash());
+        assert_eq!(game.to_fen(), game.start_fen());
+    }
+}
@@ -0,0 +1,18 @@
+// =======================================================
+// Project: GatedChess
+// File: lib.rs
+// Description: Rules of gated chess: board, pieces, gates, game state,
+//              notation and time controls, with no rendering dependencies.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+pub mod board;
+pub mod config;
+pub mod game;
+pub mod gates;
+pub mod parser;
+pub mod pieces;
+pub mod time_control;
@@ -0,0 +1,51 @@
+// =======================================================
+// Project: GatedChess
+// File: algebraic.rs
+// Description: Converts chess positions into grid values for the board.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::{Move, Position};
+use crate::pieces::PieceType;
+
+pub fn parse_square(input: &str) -> Option<Position> {
+    if input.len() != 2 { return None; }
+
+    Position::from_algebraic(input)
+}
+
+/// Reads a coordinate move such as `e2e4` or `e7e8q`, or a gate placement
+/// written `G@e4`.
+pub fn parse_move(input: &str) -> Option<Move> {
+    let input = input.trim();
+    if let Some(square) = input.strip_prefix("G@") {
+        return Some(Move::place_gate(parse_square(square)?));
+    }
+    if input.len() != 4 && input.len() != 5 { return None; }
+
+    let from = parse_square(input.get(0..2)?)?;
+    let to = parse_square(input.get(2..4)?)?;
+
+    // A fifth character names the promotion piece, e.g. `e7e8q`
+    let promotion = match input.chars().nth(4) {
+        Some(c) => Some(PieceType::from_char(c)?),
+        None => None,
+    };
+
+    Some(Move::new(from, to).with_promotion(promotion))
+}
+
+/// The inverse of `parse_move`, e.g. `e2e4`, `e7e8q` or `G@e4`.
+pub fn format_move(mv: &Move) -> String {
+    if mv.places_gate {
+        return format!("G@{}", mv.to.to_algebraic());
+    }
+    let mut text = format!("{}{}", mv.from.to_algebraic(), mv.to.to_algebraic());
+    if let Some(kind) = mv.promotion {
+        text.push(kind.to_char());
+    }
+    text
+}
@@ -8,6 +8,7 @@
 // License: MIT
 // =======================================================
 
-mod algebraic;
-mod pgn;
+pub mod algebraic;
+pub mod pgn;
+pub mod san;
 
@@ -0,0 +1,359 @@
+// =======================================================
+// Project: GatedChess
+// File: pgn.rs
+// Description: Imports and exports games as PGN.
+// Author: Seamus Daniello
+// Created: 2025-11-07
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::board::create_board;
+use crate::game::fen::{FenError, START_FEN};
+use crate::game::moves::castling::CastlingFiles;
+use crate::game::{Game, GameResult};
+use crate::gates::GateRules;
+use crate::parser::algebraic::parse_move;
+use crate::parser::san::{game_san, parse_san};
+use crate::pieces::Color;
+use crate::time_control::TimeControl;
+
+/// Value of the `Variant` tag, so other tools can tell these games apart
+/// from standard chess.
+pub const VARIANT_NAME: &str = "Gated";
+/// `Variant` of games whose kings and rooks start outside the standard
+/// layout.
+pub const CHESS960_VARIANT_NAME: &str = "Gated Chess960";
+
+const LINE_WIDTH: usize = 80;
+
+/// Tag pairs written before the moves. The Seven Tag Roster comes first,
+/// then the time control, variant and gate rules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PgnTags {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+    /// Overrides the result read from the game, for games decided off the
+    /// board by time or forfeit.
+    pub result: Option<String>,
+    pub time_control: Option<TimeControl>,
+}
+
+impl Default for PgnTags {
+    fn default() -> Self {
+        Self {
+            event: "?".to_string(),
+            site: "?".to_string(),
+            date: "????.??.??".to_string(),
+            round: "?".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+            result: None,
+            time_control: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgnError {
+    BadTag(String),
+    BadFen(FenError),
+    /// A move that doesn't resolve to a legal move in its position.
+    IllegalMove(String),
+}
+
+/// Writes `game` from its starting position up to the current move.
+pub fn export_pgn(game: &Game, tags: &PgnTags) -> String {
+    let result = tags
+        .result
+        .clone()
+        .unwrap_or_else(|| result_token(&game.result).to_string());
+
+    let mut pgn = String::new();
+    for (name, value) in [
+        ("Event", tags.event.as_str()),
+        ("Site", tags.site.as_str()),
+        ("Date", tags.date.as_str()),
+        ("Round", tags.round.as_str()),
+        ("White", tags.white.as_str()),
+        ("Black", tags.black.as_str()),
+        ("Result", result.as_str()),
+    ] {
+        push_tag(&mut pgn, name, value);
+    }
+    if let Some(tc) = tags.time_control {
+        push_tag(
+            &mut pgn,
+            "TimeControl",
+            &format!("{}+{}", tc.initial_seconds, tc.increment_seconds),
+        );
+    }
+    let variant = if game.castling_files == CastlingFiles::STANDARD {
+        VARIANT_NAME
+    } else {
+        CHESS960_VARIANT_NAME
+    };
+    push_tag(&mut pgn, "Variant", variant);
+    push_tag(&mut pgn, "GateRules", &game.gate_rules().to_notation());
+    if game.start_fen() != START_FEN {
+        push_tag(&mut pgn, "SetUp", "1");
+        push_tag(&mut pgn, "FEN", game.start_fen());
+    }
+    pgn.push('\n');
+
+    // Number the moves from the starting position's side and move number
+    let (mut move_number, mut turn) = match Game::from_fen(game.start_fen()) {
+        Ok(start) => (start.fullmove_number, start.current_turn),
+        Err(_) => (1, Color::White),
+    };
+    let mut tokens = Vec::new();
+    for (i, san) in game_san(game).into_iter().enumerate() {
+        if turn == Color::White {
+            tokens.push(format!("{}.", move_number));
+        } else {
+            if i == 0 {
+                tokens.push(format!("{}...", move_number));
+            }
+            move_number += 1;
+        }
+        tokens.push(san);
+        turn = match turn {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+    }
+    tokens.push(result);
+
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > LINE_WIDTH {
+            pgn.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            pgn.push(' ');
+            line_len += 1;
+        }
+        line_len += token.len();
+        pgn.push_str(&token);
+    }
+    pgn.push('\n');
+
+    pgn
+}
+
+/// Reads the first game in `text`, replaying its moves through
+/// `Game::make_move`, which rebuilds gate state along the way. Games without
+/// a `GateRules` tag are played under the standard rules. Either variant
+/// name is read the same way, since the castling layout comes from the FEN.
+pub fn import_pgn(text: &str) -> Result<(PgnTags, Game), PgnError> {
+    let mut tags = PgnTags::default();
+    let mut fen = None;
+    let mut gate_rules = GateRules::default();
+    let mut movetext = String::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        // Tags after the moves belong to the next game
+        if line.starts_with('[') && !movetext.trim().is_empty() {
+            break;
+        }
+        if let Some(tag) = line.strip_prefix('[') {
+            let (name, value) = parse_tag(tag).ok_or_else(|| PgnError::BadTag(line.to_string()))?;
+            match name {
+                "Event" => tags.event = value,
+                "Site" => tags.site = value,
+                "Date" => tags.date = value,
+                "Round" => tags.round = value,
+                "White" => tags.white = value,
+                "Black" => tags.black = value,
+                "Result" => tags.result = Some(value),
+                "TimeControl" => tags.time_control = parse_time_control(&value),
+                "FEN" => fen = Some(value),
+                "GateRules" => {
+                    gate_rules = GateRules::from_notation(&value)
+                        .ok_or_else(|| PgnError::BadTag(line.to_string()))?;
+                }
+                _ => {}
+            }
+        } else if !line.starts_with('%') {
+            movetext.push_str(line);
+            movetext.push('\n');
+        }
+    }
+
+    let mut game = match fen {
+        Some(fen) => Game::from_fen_with_rules(&fen, gate_rules).map_err(PgnError::BadFen)?,
+        None => Game::new(create_board(), gate_rules),
+    };
+
+    for token in movetext_tokens(&movetext) {
+        let mv = parse_san(&game, &token)
+            .or_else(|| parse_move(&token))
+            .ok_or_else(|| PgnError::IllegalMove(token.clone()))?;
+        game.make_move(mv)
+            .map_err(|_| PgnError::IllegalMove(token.clone()))?;
+    }
+
+    Ok((tags, game))
+}
+
+pub fn result_token(result: &GameResult) -> &'static str {
+    match result {
+        GameResult::Checkmate(Color::White) => "1-0",
+        GameResult::Checkmate(Color::Black) => "0-1",
+        GameResult::InProgress => "*",
+        GameResult::Stalemate
+        | GameResult::FiftyMoveRule
+        | GameResult::ThreefoldRepetition
+        | GameResult::InsufficientMaterial => "1/2-1/2",
+    }
+}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+}
+
+/// Splits `Name "Value"]` into its name and unescaped value.
+fn parse_tag(tag: &str) -> Option<(&str, String)> {
+    let tag = tag.trim_end().strip_suffix(']')?;
+    let (name, rest) = tag.split_once(char::is_whitespace)?;
+    let quoted = rest.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            value.push(chars.next()?);
+        } else {
+            value.push(c);
+        }
+    }
+    Some((name, value))
+}
+
+fn parse_time_control(value: &str) -> Option<TimeControl> {
+    let (initial, increment) = value.split_once('+').unwrap_or((value, "0"));
+    Some(TimeControl::new(initial.parse().ok()?, increment.parse().ok()?))
+}
+
+/// The moves in a block of movetext, skipping move numbers, comments,
+/// variations and annotation glyphs, up to the result.
+fn movetext_tokens(movetext: &str) -> Vec<String> {
+    let mut cleaned = String::new();
+    let mut variation_depth = 0;
+    let mut in_brace_comment = false;
+    let mut in_line_comment = false;
+
+    for c in movetext.chars() {
+        if in_line_comment {
+            in_line_comment = c != '\n';
+            continue;
+        }
+        if in_brace_comment {
+            in_brace_comment = c != '}';
+            continue;
+        }
+        match c {
+            '{' => in_brace_comment = true,
+            ';' => in_line_comment = true,
+            '(' => variation_depth += 1,
+            ')' => variation_depth -= 1,
+            _ if variation_depth > 0 => {}
+            _ => cleaned.push(c),
+        }
+    }
+
+    cleaned
+        .split_whitespace()
+        .take_while(|token| !matches!(*token, "1-0" | "0-1" | "1/2-1/2" | "*"))
+        .map(strip_move_number)
+        .filter(|token| !token.is_empty() && !token.starts_with('$'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Drops a leading move number such as `12.` or `12...`, which may be
+/// written against the move itself.
+fn strip_move_number(token: &str) -> &str {
+    let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = &token[digits..];
+    if digits > 0 && rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_gate_rules_survive_export_and_import() {
+        // Knights leave three-ply gates where they land, and each side may
+        // place one
+        let rules = GateRules {
+            spawners: [false, false, true, false, false, false],
+            duration: 3,
+            on_path: false,
+            on_origin: false,
+            on_destination: true,
+            blocks_owner: false,
+            placement_stock: 1,
+        };
+        let mut game = Game::new(create_board(), rules);
+        for text in ["g1f3", "G@e5", "f3d4", "b8c6", "d4b5"] {
+            game.make_move(parse_move(text).unwrap()).unwrap();
+        }
+        assert_eq!(game_san(&game), ["Nf3", "G@e5", "Nd4", "Nc6", "Nb5"]);
+
+        let pgn = export_pgn(&game, &PgnTags::default());
+        assert!(pgn.contains("[GateRules \"n 3 d 0 1\"]"));
+        let (_, imported) = import_pgn(&pgn).unwrap();
+        assert_eq!(imported.gate_rules(), rules);
+        assert_eq!(imported.to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn chess960_games_name_their_variant() {
+        let mut game = Game::from_fen("1k6/8/8/8/8/8/8/RK2R3 w EA - 0 1").unwrap();
+        for text in ["b1e1", "b8c8"] {
+            game.make_move(parse_move(text).unwrap()).unwrap();
+        }
+
+        let pgn = export_pgn(&game, &PgnTags::default());
+        assert!(pgn.contains("[Variant \"Gated Chess960\"]"));
+        assert!(pgn.contains("1. O-O Kc8"));
+        let (_, imported) = import_pgn(&pgn).unwrap();
+        assert_eq!(imported.to_fen(), game.to_fen());
+
+        let standard = export_pgn(&Game::from_fen(START_FEN).unwrap(), &PgnTags::default());
+        assert!(standard.contains("[Variant \"Gated\"]"));
+    }
+
+    #[test]
+    fn reads_only_the_first_game() {
+        let text = "[Event \"One\"]\n\n1. e4 e5 2. Nf3 1-0\n\n\
+                    [Event \"Two\"]\n\n1. d4 d5 *\n";
+        let (tags, game) = import_pgn(text).unwrap();
+        assert_eq!(tags.event, "One");
+        assert_eq!(game_san(&game), ["e4", "e5", "Nf3"]);
+
+        // Without tags between them, the first result still ends the game
+        let (_, game) = import_pgn("1. e4 e5 1/2-1/2\n1. d4 d5 *\n").unwrap();
+        assert_eq!(game_san(&game), ["e4", "e5"]);
+    }
+
+    #[test]
+    fn skips_comments_variations_and_glyphs() {
+        let text = "1. e4 {best by test} e5 (1... c5 2. Nf3) 2.Nf3 $1 ; a line comment\n\
+                    2... Nc6! *\n";
+        let (_, game) = import_pgn(text).unwrap();
+        assert_eq!(game_san(&game), ["e4", "e5", "Nf3", "Nc6"]);
+    }
+}
@@ -0,0 +1,251 @@
+// =======================================================
+// Project: GatedChess
+// File: san.rs
+// Description: Standard Algebraic Notation for moves.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use crate::game::moves::castling::CastlingSide;
+use crate::game::{Game, Move, Position};
+use crate::pieces::{Color, PieceType};
+
+/// Writes `mv` in SAN as played from `game`, e.g. `Nbd7`, `exd6`, `e8=Q+`
+/// or `O-O#`, and gate placements as `G@e4`. Check and mate are found by
+/// playing the move on a copy.
+pub fn to_san(game: &Game, mv: &Move) -> String {
+    // A placement blocks, but gates that expire on it may uncover a check
+    if mv.places_gate {
+        let mut after = game.scratch_copy();
+        after.place_gate_unchecked(mv.to);
+        let opponent = match game.current_turn {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        return format!("G@{}{}", mv.to.to_algebraic(), check_suffix(&after, opponent));
+    }
+    let mv = game.describe_move(mv.from, mv.to, mv.promotion);
+    let Some(piece) = mv.piece else {
+        return format!("{}{}", mv.from.to_algebraic(), mv.to.to_algebraic());
+    };
+
+    let mut san = match mv.castling {
+        Some(CastlingSide::Kingside) => "O-O".to_string(),
+        Some(CastlingSide::Queenside) => "O-O-O".to_string(),
+        None => {
+            let mut san = String::new();
+            if piece.kind == PieceType::Pawn {
+                if mv.captured.is_some() {
+                    san.push(file_char(mv.from));
+                }
+            } else {
+                san.push(piece.kind.to_char().to_ascii_uppercase());
+                san.push_str(&disambiguation(game, &mv, piece.kind, piece.color));
+            }
+            if mv.captured.is_some() {
+                san.push('x');
+            }
+            san.push_str(&mv.to.to_algebraic());
+            if let Some(kind) = mv.promotion {
+                san.push('=');
+                san.push(kind.to_char().to_ascii_uppercase());
+            }
+            san
+        }
+    };
+
+    let opponent = match piece.color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    };
+    let mut after = game.scratch_copy();
+    after.make_move_unchecked(mv.from, mv.to, mv.promotion);
+    san.push_str(check_suffix(&after, opponent));
+
+    san
+}
+
+/// `+` or `#` when `color` is in check or mated in `after`, the position
+/// once a move is played.
+fn check_suffix(after: &Game, color: Color) -> &'static str {
+    if !after.is_king_in_check(color) {
+        ""
+    } else if after.get_all_legal_moves(color).is_empty() {
+        "#"
+    } else {
+        "+"
+    }
+}
+
+/// SAN for every move played in `game`, replayed from its starting
+/// position under its gate rules.
+pub fn game_san(game: &Game) -> Vec<String> {
+    let mut sans = Vec::new();
+    let Ok(mut replay) = Game::from_fen_with_rules(game.start_fen(), game.gate_rules()) else {
+        return sans;
+    };
+
+    for mv in game.move_list() {
+        sans.push(to_san(&replay, &mv));
+        if replay.make_move(mv).is_err() {
+            break;
+        }
+    }
+    sans
+}
+
+/// Finds the legal move for the side to move that `text` names. Accepts the
+/// usual looser forms too: a missing `x`, `e8Q` for `e8=Q`, `0-0` for `O-O`,
+/// extra disambiguation, and trailing `+`, `#`, `!` or `?`.
+pub fn parse_san(game: &Game, text: &str) -> Option<Move> {
+    let text = text.trim().trim_end_matches(['+', '#', '!', '?']);
+    let legal = game.get_all_legal_moves(game.current_turn);
+
+    if let Some(square) = text.strip_prefix("G@") {
+        let to = Position::from_algebraic(square)?;
+        return legal.into_iter().find(|mv| mv.places_gate && mv.to == to);
+    }
+
+    let castling = match text {
+        "O-O" | "0-0" => Some(CastlingSide::Kingside),
+        "O-O-O" | "0-0-0" => Some(CastlingSide::Queenside),
+        _ => None,
+    };
+    if castling.is_some() {
+        return legal.into_iter().find(|mv| mv.castling == castling);
+    }
+
+    let mut chars: Vec<char> = text.chars().filter(|c| !matches!(c, 'x' | ':' | '-')).collect();
+
+    // Promotion, written `e8=Q` or `e8Q`
+    let mut promotion = None;
+    if let Some(&last) = chars.last()
+        && "QRBN".contains(last)
+        && chars.len() > 2
+    {
+        promotion = Some(PieceType::from_char(last)?);
+        chars.pop();
+        if chars.last() == Some(&'=') {
+            chars.pop();
+        }
+    }
+
+    // Piece letters are upper case, so a leading `b` is always a pawn file
+    let kind = match chars.first() {
+        Some(&c) if "KQRBN".contains(c) => {
+            chars.remove(0);
+            PieceType::from_char(c)?
+        }
+        _ => PieceType::Pawn,
+    };
+
+    if chars.len() < 2 {
+        return None;
+    }
+    let to_text: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = Position::from_algebraic(&to_text)?;
+
+    let mut from_col = None;
+    let mut from_row = None;
+    for c in chars {
+        match c {
+            'a'..='h' => from_col = Some(c as usize - 'a' as usize),
+            '1'..='8' => from_row = Some(c as usize - '1' as usize),
+            _ => return None,
+        }
+    }
+
+    let mut candidates = legal.into_iter().filter(|mv| {
+        mv.to == to
+            && mv.promotion == promotion
+            && mv.piece.map(|p| p.kind) == Some(kind)
+            && from_col.is_none_or(|col| mv.from.col == col)
+            && from_row.is_none_or(|row| mv.from.row == row)
+    });
+
+    let found = candidates.next()?;
+    // An ambiguous move names nothing
+    if candidates.next().is_some() {
+        return None;
+    }
+    Some(found)
+}
+
+/// The file, rank or square needed to tell `mv` apart from other legal moves
+/// of the same kind of piece to the same square.
+fn disambiguation(game: &Game, mv: &Move, kind: PieceType, color: Color) -> String {
+    let rivals: Vec<Position> = game
+        .get_all_legal_moves(color)
+        .into_iter()
+        .filter(|other| {
+            other.to == mv.to
+                && other.from != mv.from
+                && other.piece.map(|p| p.kind) == Some(kind)
+        })
+        .map(|other| other.from)
+        .collect();
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|r| r.col != mv.from.col) {
+        file_char(mv.from).to_string()
+    } else if rivals.iter().all(|r| r.row != mv.from.row) {
+        (mv.from.row + 1).to_string()
+    } else {
+        mv.from.to_algebraic()
+    }
+}
+
+fn file_char(pos: Position) -> char {
+    (b'a' + pos.col as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::algebraic::parse_move;
+
+    fn san(fen: &str, text: &str) -> String {
+        let game = Game::from_fen(fen).unwrap();
+        to_san(&game, &parse_move(text).unwrap())
+    }
+
+    #[test]
+    fn disambiguates_by_file_then_rank_then_square() {
+        assert_eq!(san("7k/8/8/8/8/8/8/R4R1K w - - 0 1", "a1c1"), "Rac1");
+        assert_eq!(san("7k/8/8/R7/8/8/8/R6K w - - 0 1", "a1a3"), "R1a3");
+        assert_eq!(san("6k1/8/8/8/8/Q7/8/Q1Q4K w - - 0 1", "a1b2"), "Qa1b2");
+
+        let game = Game::from_fen("7k/8/8/R7/8/8/8/R6K w - - 0 1").unwrap();
+        assert!(parse_san(&game, "Ra3").is_none());
+        assert_eq!(parse_san(&game, "R5a3").unwrap().from.to_algebraic(), "a5");
+    }
+
+    #[test]
+    fn placements_show_checks_from_expiring_gates() {
+        let fen = "R3k3/8/8/8/8/8/8/4K3 w - - 0 1 c8:1b 1/0";
+        assert_eq!(san(fen, "G@h4"), "G@h4+");
+        assert_eq!(san(&fen.replace("c8:1b", "c8:2b"), "G@h4"), "G@h4");
+    }
+
+    #[test]
+    fn reads_and_writes_promotions() {
+        let fen = "3r2k1/4P3/8/8/8/8/8/6K1 w - - 0 1";
+        assert_eq!(san(fen, "e7d8q"), "exd8=Q+");
+        assert_eq!(san(fen, "e7e8n"), "e8=N");
+
+        let game = Game::from_fen(fen).unwrap();
+        for (text, to, kind) in [
+            ("exd8=Q+", "d8", PieceType::Queen),
+            ("exd8N", "d8", PieceType::Knight),
+            ("e8=R", "e8", PieceType::Rook),
+            ("e8B", "e8", PieceType::Bishop),
+        ] {
+            let mv = parse_san(&game, text).unwrap();
+            assert_eq!((mv.to.to_algebraic().as_str(), mv.promotion), (to, Some(kind)), "{}", text);
+        }
+        assert!(parse_san(&game, "e8").is_none());
+    }
+}
@@ -0,0 +1,35 @@
+use super::piece_fsm::{PieceFSM, PieceState, StateClock};
+use crate::pieces::Piece;
+
+pub struct KingFSM {
+    piece: Piece,
+    clock: StateClock,
+}
+
+impl KingFSM {
+    pub fn new(piece: Piece) -> Self {
+        Self {
+            piece,
+            clock: StateClock::new(),
+        }
+    }
+}
+
+impl PieceFSM for KingFSM {
+    fn piece(&self) -> &Piece {
+        &self.piece
+    }
+
+    fn clock(&self) -> &StateClock {
+        &self.clock
+    }
+
+    fn clock_mut(&mut self) -> &mut StateClock {
+        &mut self.clock
+    }
+
+    /// The only piece that can be in check, and never a promotion.
+    fn accepts(&self, state: PieceState) -> bool {
+        state != PieceState::Promoted
+    }
+}
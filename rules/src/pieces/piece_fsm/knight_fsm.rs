@@ -0,0 +1,30 @@
+use super::piece_fsm::{PieceFSM, StateClock};
+use crate::pieces::Piece;
+
+pub struct KnightFSM {
+    piece: Piece,
+    clock: StateClock,
+}
+
+impl KnightFSM {
+    pub fn new(piece: Piece) -> Self {
+        Self {
+            piece,
+            clock: StateClock::new(),
+        }
+    }
+}
+
+impl PieceFSM for KnightFSM {
+    fn piece(&self) -> &Piece {
+        &self.piece
+    }
+
+    fn clock(&self) -> &StateClock {
+        &self.clock
+    }
+
+    fn clock_mut(&mut self) -> &mut StateClock {
+        &mut self.clock
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod piece_fsm;
 
 pub mod bishop_fsm;
@@ -11,6 +12,6 @@ pub use bishop_fsm::BishopFSM;
 pub use king_fsm::KingFSM;
 pub use knight_fsm::KnightFSM;
 pub use pawn_fsm::PawnFSM;
-pub use piece_fsm::PieceFSM;
+pub use piece_fsm::{PieceFSM, PieceState, StateClock};
 pub use queen_fsm::QueenFSM;
 pub use rook_fsm::RookFSM;
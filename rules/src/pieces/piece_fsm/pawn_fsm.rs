@@ -0,0 +1,35 @@
+use super::piece_fsm::{PieceFSM, PieceState, StateClock};
+use crate::pieces::Piece;
+
+pub struct PawnFSM {
+    piece: Piece,
+    clock: StateClock,
+}
+
+impl PawnFSM {
+    pub fn new(piece: Piece) -> Self {
+        Self {
+            piece,
+            clock: StateClock::new(),
+        }
+    }
+}
+
+impl PieceFSM for PawnFSM {
+    fn piece(&self) -> &Piece {
+        &self.piece
+    }
+
+    fn clock(&self) -> &StateClock {
+        &self.clock
+    }
+
+    fn clock_mut(&mut self) -> &mut StateClock {
+        &mut self.clock
+    }
+
+    /// Never in check, and a promotion hands over to the new piece's FSM.
+    fn accepts(&self, state: PieceState) -> bool {
+        !matches!(state, PieceState::InCheck | PieceState::Promoted)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::pieces::Piece;
+
+/// What a piece is doing, as far as drawing it goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PieceState {
+    Idle,
+    Selected,
+    Moving,
+    Capturing,
+    /// Taken off the board. Only retired FSMs are ever in it.
+    Captured,
+    /// A king under attack, until the next move.
+    InCheck,
+    Promoted,
+}
+
+/// The state a piece is in and how long it has been there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StateClock {
+    pub state: PieceState,
+    /// Seconds since `state` was entered.
+    pub elapsed: f32,
+}
+
+impl StateClock {
+    pub fn new() -> Self {
+        Self {
+            state: PieceState::Idle,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn enter(&mut self, state: PieceState) {
+        self.state = state;
+        self.elapsed = 0.0;
+    }
+}
+
+impl Default for StateClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Every piece's state machine. Game events and selection move it between
+/// states through `set_state`; `update_state` moves time on and drops timed
+/// states back to `Idle`. The kinds differ in which states they take. How
+/// long a state lasts is up to whoever draws the pieces.
+pub trait PieceFSM: Send {
+    fn piece(&self) -> &Piece;
+
+    fn clock(&self) -> &StateClock;
+    fn clock_mut(&mut self) -> &mut StateClock;
+
+    /// Whether this kind of piece has `state` at all. Only kings can be in
+    /// check, and every piece a pawn promotes to can arrive promoted.
+    fn accepts(&self, state: PieceState) -> bool {
+        state != PieceState::InCheck
+    }
+
+    fn state(&self) -> PieceState {
+        self.clock().state
+    }
+
+    /// Seconds spent in the current state.
+    fn time_in_state(&self) -> f32 {
+        self.clock().elapsed
+    }
+
+    /// Enters `state` from the start, if this kind of piece has it.
+    fn set_state(&mut self, state: PieceState) {
+        if self.accepts(state) {
+            self.clock_mut().enter(state);
+        }
+    }
+
+    /// Moves time on by `dt` seconds, going back to `Idle` once `duration`
+    /// has passed. `None` holds the state until something else happens.
+    fn update_state(&mut self, dt: f32, duration: Option<f32>) {
+        let clock = self.clock_mut();
+        clock.elapsed += dt;
+        if duration.is_some_and(|limit| clock.elapsed >= limit) {
+            clock.enter(PieceState::Idle);
+        }
+    }
+}
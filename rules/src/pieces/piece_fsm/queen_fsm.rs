@@ -0,0 +1,30 @@
+use super::piece_fsm::{PieceFSM, StateClock};
+use crate::pieces::Piece;
+
+pub struct QueenFSM {
+    piece: Piece,
+    clock: StateClock,
+}
+
+impl QueenFSM {
+    pub fn new(piece: Piece) -> Self {
+        Self {
+            piece,
+            clock: StateClock::new(),
+        }
+    }
+}
+
+impl PieceFSM for QueenFSM {
+    fn piece(&self) -> &Piece {
+        &self.piece
+    }
+
+    fn clock(&self) -> &StateClock {
+        &self.clock
+    }
+
+    fn clock_mut(&mut self) -> &mut StateClock {
+        &mut self.clock
+    }
+}
@@ -0,0 +1,187 @@
+// =======================================================
+// Project: GatedChess
+// File: mod.rs
+// Description: Computer opponent that searches on its own thread.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+pub mod search;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::game::{Game, Move};
+use crate::pieces::Color;
+use search::{SearchLimits, SearchReport, Searcher};
+
+/// How hard the computer plays. Stronger settings search deeper and may
+/// think for longer, as far as the clock allows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Strength {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Strength {
+    pub const ALL: [Strength; 3] = [Strength::Easy, Strength::Medium, Strength::Hard];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Strength::Easy => "Easy",
+            Strength::Medium => "Medium",
+            Strength::Hard => "Hard",
+        }
+    }
+
+    fn max_depth(&self) -> u32 {
+        match self {
+            Strength::Easy => 1,
+            Strength::Medium => 3,
+            Strength::Hard => 64,
+        }
+    }
+
+    fn max_think_seconds(&self) -> f64 {
+        match self {
+            Strength::Easy => 0.5,
+            Strength::Medium => 2.0,
+            Strength::Hard => 8.0,
+        }
+    }
+
+    /// Limits for one move with `remaining` seconds on the clock and
+    /// `increment` coming back after the move.
+    pub fn limits(&self, remaining: f64, increment: f64) -> SearchLimits {
+        let budget = clock_budget(remaining, increment).min(self.max_think_seconds());
+        SearchLimits {
+            max_depth: self.max_depth(),
+            time_limit: Some(Duration::from_secs_f64(budget)),
+        }
+    }
+}
+
+/// Seconds to spend on one move with `remaining` seconds on the clock and
+/// `increment` coming back after it. Assumes about thirty moves are still to
+/// be played, and never spends more than half of what is left.
+pub fn clock_budget(remaining: f64, increment: f64) -> f64 {
+    (remaining / 30.0 + increment * 0.8)
+        .min(remaining * 0.5)
+        .max(0.05)
+}
+
+enum SearchCommand {
+    Search {
+        game: Box<Game>,
+        limits: SearchLimits,
+        stop: Arc<AtomicBool>,
+    },
+    Shutdown,
+}
+
+/// Plays one colour. Searches run on a worker thread, so the render loop
+/// only starts a search and polls for the answer.
+pub struct ComputerPlayer {
+    color: Color,
+    strength: Strength,
+    tx: Sender<SearchCommand>,
+    rx: Receiver<SearchReport>,
+    /// Stop flag of the search in progress, if any.
+    stop: Option<Arc<AtomicBool>>,
+    /// Results still to come from cancelled searches, which are dropped.
+    stale_results: usize,
+}
+
+impl ComputerPlayer {
+    pub fn new(color: Color, strength: Strength) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (report_tx, report_rx) = mpsc::channel();
+        thread::spawn(move || run_worker(cmd_rx, report_tx));
+
+        Self {
+            color,
+            strength,
+            tx: cmd_tx,
+            rx: report_rx,
+            stop: None,
+            stale_results: 0,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn strength(&self) -> Strength {
+        self.strength
+    }
+
+    pub fn is_thinking(&self) -> bool {
+        self.stop.is_some()
+    }
+
+    /// Starts searching the position in `game` for the side to move.
+    pub fn start_thinking(&mut self, game: &Game, limits: SearchLimits) {
+        self.cancel();
+        let stop = Arc::new(AtomicBool::new(false));
+        self.stop = Some(Arc::clone(&stop));
+        let _ = self.tx.send(SearchCommand::Search {
+            game: Box::new(game.scratch_copy()),
+            limits,
+            stop,
+        });
+    }
+
+    /// Stops the search in progress, if any, and forgets its answer.
+    pub fn cancel(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            stop.store(true, Ordering::Relaxed);
+            self.stale_results += 1;
+        }
+    }
+
+    /// The move found by the current search, once it has finished.
+    pub fn try_recv(&mut self) -> Option<Move> {
+        while let Ok(report) = self.rx.try_recv() {
+            if self.stale_results > 0 {
+                self.stale_results -= 1;
+                continue;
+            }
+            self.stop = None;
+            return report.best_move;
+        }
+        None
+    }
+}
+
+impl Drop for ComputerPlayer {
+    fn drop(&mut self) {
+        self.cancel();
+        let _ = self.tx.send(SearchCommand::Shutdown);
+    }
+}
+
+fn run_worker(cmd_rx: Receiver<SearchCommand>, report_tx: Sender<SearchReport>) {
+    let mut searcher = Searcher::new();
+    while let Ok(command) = cmd_rx.recv() {
+        match command {
+            SearchCommand::Search {
+                mut game,
+                limits,
+                stop,
+            } => {
+                let report = searcher.search(&mut game, &limits, stop);
+                if report_tx.send(report).is_err() {
+                    return;
+                }
+            }
+            SearchCommand::Shutdown => return,
+        }
+    }
+}
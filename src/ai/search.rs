@@ -0,0 +1,409 @@
+// =======================================================
+// Project: GatedChess
+// File: search.rs
+// Description: Iterative-deepening alpha-beta search.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::eval::{evaluate, piece_value};
+use crate::game::draw_rules::FIFTY_MOVE_LIMIT;
+use crate::game::{Game, Move};
+use crate::pieces::PieceType;
+
+/// Score for delivering mate now. Mates further away score a little less,
+/// so the search prefers the quickest one.
+pub const MATE_SCORE: i32 = 100_000;
+const INFINITY: i32 = 1_000_000;
+const TABLE_SIZE: usize = 1 << 16;
+/// Nodes between checks of the clock and stop flag.
+const CHECK_INTERVAL: u64 = 2048;
+
+#[derive(Clone, Debug)]
+pub struct SearchLimits {
+    pub max_depth: u32,
+    pub time_limit: Option<Duration>,
+}
+
+/// Result of the deepest iteration that finished.
+#[derive(Clone, Debug)]
+pub struct SearchReport {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone)]
+struct TableEntry {
+    key: u64,
+    depth: u32,
+    score: i32,
+    bound: Bound,
+    best_move: Option<Move>,
+}
+
+/// Searches positions for the best move. Keeps its transposition table
+/// between searches, so one searcher should be reused for a whole game.
+pub struct Searcher {
+    table: Vec<Option<TableEntry>>,
+    nodes: u64,
+    deadline: Option<Instant>,
+    stop: Arc<AtomicBool>,
+    aborted: bool,
+}
+
+impl Searcher {
+    pub fn new() -> Self {
+        Self {
+            table: vec![None; TABLE_SIZE],
+            nodes: 0,
+            deadline: None,
+            stop: Arc::new(AtomicBool::new(false)),
+            aborted: false,
+        }
+    }
+
+    /// Deepens one ply at a time until `limits` or `stop` ends the search,
+    /// then reports the last iteration that completed. `game` is left as it
+    /// was found.
+    pub fn search(
+        &mut self,
+        game: &mut Game,
+        limits: &SearchLimits,
+        stop: Arc<AtomicBool>,
+    ) -> SearchReport {
+        self.search_with_progress(game, limits, stop, |_| {})
+    }
+
+    /// Like `search`, calling `on_iteration` with the report of every
+    /// iteration as it completes.
+    pub fn search_with_progress(
+        &mut self,
+        game: &mut Game,
+        limits: &SearchLimits,
+        stop: Arc<AtomicBool>,
+        mut on_iteration: impl FnMut(&SearchReport),
+    ) -> SearchReport {
+        self.nodes = 0;
+        self.deadline = limits.time_limit.map(|limit| Instant::now() + limit);
+        self.stop = stop;
+        self.aborted = false;
+
+        let mut report = SearchReport {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+        };
+
+        for depth in 1..=limits.max_depth.max(1) {
+            let (best_move, score) = self.search_root(game, depth);
+            if self.aborted {
+                // A cut-short iteration is only better than nothing
+                if report.best_move.is_none() {
+                    report.best_move = best_move;
+                }
+                break;
+            }
+
+            report = SearchReport {
+                best_move,
+                score,
+                depth,
+                nodes: self.nodes,
+            };
+            on_iteration(&report);
+            // Nothing deeper will find a faster mate
+            if score.abs() >= MATE_SCORE - depth as i32 {
+                break;
+            }
+        }
+
+        report.nodes = self.nodes;
+        report
+    }
+
+    fn search_root(&mut self, game: &mut Game, depth: u32) -> (Option<Move>, i32) {
+        let mut moves = game.get_all_legal_moves(game.current_turn);
+        self.order_moves(game, &mut moves);
+
+        let mut alpha = -INFINITY;
+        let mut best_move = None;
+        for mv in moves {
+            let score = -self.play(game, &mv, |searcher, game| {
+                searcher.negamax(game, depth - 1, 1, -INFINITY, -alpha)
+            });
+            if self.aborted {
+                break;
+            }
+            if score > alpha || best_move.is_none() {
+                alpha = score;
+                best_move = Some(mv);
+            }
+        }
+
+        if !self.aborted {
+            self.store(game.position_key(), depth, alpha, Bound::Exact, best_move.clone());
+        }
+        (best_move, alpha)
+    }
+
+    fn negamax(&mut self, game: &mut Game, depth: u32, ply: u32, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+        if is_draw(game) {
+            return 0;
+        }
+        if depth == 0 {
+            return self.quiescence(game, alpha, beta);
+        }
+
+        let key = game.position_key();
+        if let Some(entry) = self.probe(key)
+            && entry.depth >= depth
+        {
+            let score = from_table(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        let mut moves = game.get_all_legal_moves(game.current_turn);
+        if moves.is_empty() {
+            return if game.is_king_in_check(game.current_turn) {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+        self.order_moves(game, &mut moves);
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        for mv in moves {
+            let score = -self.play(game, &mv, |searcher, game| {
+                searcher.negamax(game, depth - 1, ply + 1, -beta, -alpha)
+            });
+            if self.aborted {
+                return 0;
+            }
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.store(key, depth, to_table(best_score, ply), bound, best_move);
+        best_score
+    }
+
+    /// Plays out captures and queen promotions until the position is quiet,
+    /// so the search never stops in the middle of an exchange.
+    fn quiescence(&mut self, game: &mut Game, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        let stand_pat = evaluate(game).relative_to(game.current_turn);
+        if stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut moves: Vec<Move> = game
+            .get_all_legal_moves(game.current_turn)
+            .into_iter()
+            .filter(|mv| mv.captured.is_some() || mv.promotion == Some(PieceType::Queen))
+            .collect();
+        moves.sort_by_key(|mv| -capture_order(mv));
+
+        for mv in moves {
+            let score = -self.play(game, &mv, |searcher, game| {
+                searcher.quiescence(game, -beta, -alpha)
+            });
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+
+    /// Plays `mv`, runs `inner` on the new position and takes the move back.
+    fn play(
+        &mut self,
+        game: &mut Game,
+        mv: &Move,
+        inner: impl FnOnce(&mut Self, &mut Game) -> i32,
+    ) -> i32 {
+        game.play_unchecked(mv);
+        let score = inner(self, game);
+        game.unmake_move_unchecked();
+        score
+    }
+
+    /// Best move from the table first, then captures of the most valuable
+    /// piece by the least valuable attacker, then the rest.
+    fn order_moves(&self, game: &Game, moves: &mut [Move]) {
+        let table_move = self
+            .probe(game.position_key())
+            .and_then(|entry| entry.best_move.clone());
+        moves.sort_by_key(|mv| {
+            if table_move.as_ref().is_some_and(|best| best.matches(mv)) {
+                -INFINITY
+            } else {
+                -capture_order(mv)
+            }
+        });
+    }
+
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            let out_of_time = self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if out_of_time || self.stop.load(Ordering::Relaxed) {
+                self.aborted = true;
+            }
+        }
+        self.aborted
+    }
+
+    fn probe(&self, key: u64) -> Option<&TableEntry> {
+        self.table[key as usize % TABLE_SIZE]
+            .as_ref()
+            .filter(|entry| entry.key == key)
+    }
+
+    fn store(&mut self, key: u64, depth: u32, score: i32, bound: Bound, best_move: Option<Move>) {
+        let slot = &mut self.table[key as usize % TABLE_SIZE];
+        // Keep deeper results for the same position
+        if slot.as_ref().is_some_and(|entry| entry.key == key && entry.depth > depth) {
+            return;
+        }
+        *slot = Some(TableEntry {
+            key,
+            depth,
+            score,
+            bound,
+            best_move,
+        });
+    }
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_draw(game: &Game) -> bool {
+    game.halfmove_clock >= FIFTY_MOVE_LIMIT
+        || game.repetition_count() >= 2
+        || game.is_insufficient_material()
+}
+
+fn capture_order(mv: &Move) -> i32 {
+    let victim = mv.captured.map_or(0, |piece| piece_value(piece.kind));
+    let attacker = mv.piece.map_or(0, |piece| piece_value(piece.kind));
+    let promotion = mv.promotion.map_or(0, piece_value);
+    if victim == 0 && promotion == 0 {
+        return 0;
+    }
+    victim * 10 - attacker / 10 + promotion
+}
+
+/// Mate scores are stored relative to the node, not the root, so they stay
+/// right when the position turns up at another ply.
+fn to_table(score: i32, ply: u32) -> i32 {
+    if score >= MATE_SCORE - 1000 {
+        score + ply as i32
+    } else if score <= -MATE_SCORE + 1000 {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn from_table(score: i32, ply: u32) -> i32 {
+    if score >= MATE_SCORE - 1000 {
+        score - ply as i32
+    } else if score <= -MATE_SCORE + 1000 {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best_move(fen: &str, depth: u32) -> SearchReport {
+        let mut game = Game::from_fen(fen).unwrap();
+        let limits = SearchLimits {
+            max_depth: depth,
+            time_limit: None,
+        };
+        Searcher::new().search(&mut game, &limits, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[test]
+    fn finds_mate_in_one() {
+        let report = best_move("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 3);
+        let mv = report.best_move.unwrap();
+        assert_eq!((mv.from.to_algebraic(), mv.to.to_algebraic()), ("a1".into(), "a8".into()));
+        assert_eq!(report.score, MATE_SCORE - 1);
+    }
+
+    #[test]
+    fn takes_a_hanging_queen() {
+        let report = best_move("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", 3);
+        let mv = report.best_move.unwrap();
+        assert_eq!(mv.to.to_algebraic(), "d5");
+    }
+
+    #[test]
+    fn leaves_the_game_as_it_found_it() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+        let limits = SearchLimits {
+            max_depth: 3,
+            time_limit: None,
+        };
+        Searcher::new().search(&mut game, &limits, Arc::new(AtomicBool::new(false)));
+        assert_eq!(game.to_fen(), fen);
+    }
+}
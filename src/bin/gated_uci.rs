@@ -0,0 +1,281 @@
+// =======================================================
+// Project: GatedChess
+// File: gated_uci.rs
+// Description: Headless engine speaking a UCI-style protocol.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Reads commands from stdin and answers on stdout, close enough to UCI for
+//! tournament scripts and GUIs that let you type a command line.
+//!
+//! ```text
+//! uci | isready | ucinewgame | quit
+//! setoption name GateRules value <rules>
+//! position (startpos | fen <fen> [<gates>]) [moves <move>...]
+//! go [depth <n>] [movetime <ms>] [wtime <ms>] [btime <ms>] [winc <ms>] [binc <ms>] [infinite]
+//! stop
+//! ```
+//!
+//! Positions take the gated FEN, with the optional seventh field listing
+//! gates as `square:duration` with an optional `w` or `b` owner, or as walls,
+//! one-way gates and portals, and an eighth field giving the gates each side
+//! has left to place. Chess960 positions name their castling rooks by file,
+//! as in `HAha`. Games use the standard gate rules unless the `GateRules`
+//! option sets others, written as in the PGN tag, such as `rb 2 p 1 0`, and
+//! applied from the next `position`. Moves are coordinate
+//! moves such as `e2e4` and `e7e8q`, or `G@e4` to place a gate, and gates
+//! age after each one just as they do in the game window. Outside standard
+//! chess a castle is the king moving onto its own rook, such as `g1h1`.
+//! Every finished iteration prints an `info` line, and each search ends with
+//! `bestmove`.
+
+use std::io::{self, BufRead};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use gated_chess::ai::clock_budget;
+use gated_chess::ai::search::{MATE_SCORE, SearchLimits, SearchReport, Searcher};
+use gated_chess::board::create_board;
+use gated_chess::game::Game;
+use gated_chess::game::fen::START_FEN;
+use gated_chess::gates::GateRules;
+use gated_chess::parser::algebraic::{format_move, parse_move};
+use gated_chess::pieces::Color;
+
+/// Depth searched when `go` sets none.
+const MAX_DEPTH: u32 = 64;
+
+struct RunningSearch {
+    stop: Arc<AtomicBool>,
+    /// Set when nothing but `stop` would end the search.
+    infinite: bool,
+    /// Hands the searcher back, tables and all, once the search is over.
+    handle: JoinHandle<Searcher>,
+}
+
+struct Engine {
+    game: Game,
+    gate_rules: GateRules,
+    /// `None` while a search thread has it.
+    searcher: Option<Searcher>,
+    search: Option<RunningSearch>,
+}
+
+impl Engine {
+    fn new() -> Self {
+        Self {
+            game: Game::from_fen(START_FEN).unwrap(),
+            gate_rules: GateRules::default(),
+            searcher: Some(Searcher::new()),
+            search: None,
+        }
+    }
+
+    /// Runs one command line. Returns false once the engine should exit.
+    fn handle(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = tokens.split_first() else {
+            return true;
+        };
+
+        match command {
+            "uci" => {
+                println!("id name GatedChess");
+                println!("id author Seamus Daniello");
+                println!(
+                    "option name GateRules type string default {}",
+                    GateRules::default().to_notation()
+                );
+                println!("uciok");
+            }
+            "isready" => println!("readyok"),
+            "ucinewgame" => {
+                self.finish_search();
+                self.game = Game::new(create_board(), self.gate_rules);
+                self.searcher = Some(Searcher::new());
+            }
+            "setoption" => self.set_option(args),
+            "position" => {
+                self.finish_search();
+                match parse_position(args, self.gate_rules) {
+                    Ok(game) => self.game = game,
+                    Err(err) => println!("info string {}", err),
+                }
+            }
+            "go" => self.go(args),
+            "stop" => self.stop_search(),
+            "quit" => {
+                self.stop_search();
+                return false;
+            }
+            _ => println!("info string unknown command {}", command),
+        }
+        true
+    }
+
+    fn set_option(&mut self, args: &[&str]) {
+        let value_at = args.iter().position(|token| *token == "value");
+        let name = args.get(1..value_at.unwrap_or(args.len())).unwrap_or_default().join(" ");
+        let value = value_at.map_or(String::new(), |i| args[i + 1..].join(" "));
+        if !name.eq_ignore_ascii_case("GateRules") {
+            println!("info string unknown option {}", name);
+            return;
+        }
+        match GateRules::from_notation(&value) {
+            Some(rules) => self.gate_rules = rules,
+            None => println!("info string bad gate rules {}", value),
+        }
+    }
+
+    fn go(&mut self, args: &[&str]) {
+        self.finish_search();
+        // The search has nothing to score without a move to play
+        let turn = self.game.current_turn;
+        if self.game.get_all_legal_moves(turn).is_empty() {
+            let score = if self.game.is_king_in_check(turn) {
+                "mate 0"
+            } else {
+                "cp 0"
+            };
+            println!("info depth 0 score {}", score);
+            println!("bestmove 0000");
+            return;
+        }
+
+        let limits = parse_limits(args, turn);
+        let infinite = limits.time_limit.is_none() && limits.max_depth == MAX_DEPTH;
+        let mut searcher = self.searcher.take().unwrap_or_default();
+        let mut game = self.game.scratch_copy();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || {
+            let started = Instant::now();
+            let report = searcher.search_with_progress(&mut game, &limits, thread_stop, |report| {
+                println!("{}", info_line(report, started.elapsed()));
+            });
+            let best = report.best_move.as_ref().map_or("0000".to_string(), format_move);
+            println!("bestmove {}", best);
+            searcher
+        });
+        self.search = Some(RunningSearch {
+            stop,
+            infinite,
+            handle,
+        });
+    }
+
+    /// Stops the search in progress, if any, and waits for its `bestmove`.
+    fn stop_search(&mut self) {
+        if let Some(search) = &self.search {
+            search.stop.store(true, Ordering::Relaxed);
+        }
+        self.finish_search();
+    }
+
+    /// Lets the search in progress run to its limits, as commands that
+    /// arrive during a search wait for it. Searches without limits are
+    /// stopped instead.
+    fn finish_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            if search.infinite {
+                search.stop.store(true, Ordering::Relaxed);
+            }
+            self.searcher = search.handle.join().ok();
+        }
+    }
+}
+
+/// Sets up a position under `gate_rules`. The start position gets the
+/// rules' full stock of gates to place; a FEN brings its own.
+fn parse_position(args: &[&str], gate_rules: GateRules) -> Result<Game, String> {
+    let moves_at = args.iter().position(|token| *token == "moves").unwrap_or(args.len());
+    let mut game = match args.first() {
+        Some(&"startpos") => Game::new(create_board(), gate_rules),
+        Some(&"fen") => Game::from_fen_with_rules(&args[1..moves_at].join(" "), gate_rules)
+            .map_err(|err| format!("bad fen {:?}", err))?,
+        _ => return Err("position needs startpos or fen".to_string()),
+    };
+
+    for text in args.iter().skip(moves_at + 1) {
+        let mv = parse_move(text).ok_or_else(|| format!("bad move {}", text))?;
+        game.make_move(mv)
+            .map_err(|err| format!("illegal move {} {:?}", text, err))?;
+    }
+    Ok(game)
+}
+
+fn parse_limits(args: &[&str], turn: Color) -> SearchLimits {
+    let value = |name: &str| {
+        args.iter()
+            .position(|token| *token == name)
+            .and_then(|i| args.get(i + 1))
+            .and_then(|text| text.parse::<u64>().ok())
+    };
+    let seconds = |name: &str| value(name).map(|ms| ms as f64 / 1000.0);
+
+    let (time, increment) = match turn {
+        Color::White => (seconds("wtime"), seconds("winc")),
+        Color::Black => (seconds("btime"), seconds("binc")),
+    };
+    let time_limit = if args.contains(&"infinite") {
+        None
+    } else if let Some(ms) = value("movetime") {
+        Some(Duration::from_millis(ms))
+    } else {
+        time.map(|remaining| {
+            Duration::from_secs_f64(clock_budget(remaining, increment.unwrap_or(0.0)))
+        })
+    };
+
+    SearchLimits {
+        max_depth: value("depth").map_or(MAX_DEPTH, |depth| depth as u32),
+        time_limit,
+    }
+}
+
+fn info_line(report: &SearchReport, elapsed: Duration) -> String {
+    let millis = elapsed.as_millis().max(1);
+    let mut line = format!(
+        "info depth {} score {} nodes {} nps {} time {}",
+        report.depth,
+        format_score(report.score),
+        report.nodes,
+        report.nodes as u128 * 1000 / millis,
+        millis
+    );
+    if let Some(mv) = &report.best_move {
+        line.push_str(&format!(" pv {}", format_move(mv)));
+    }
+    line
+}
+
+/// `cp` in centipawns, or `mate` in moves (negative when getting mated).
+fn format_score(score: i32) -> String {
+    let plies = MATE_SCORE - score.abs();
+    if plies <= 1000 {
+        let moves = (plies + 1) / 2;
+        format!("mate {}", if score > 0 { moves } else { -moves })
+    } else {
+        format!("cp {}", score)
+    }
+}
+
+fn main() {
+    let mut engine = Engine::new();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if !engine.handle(&line) {
+            return;
+        }
+    }
+    // Out of input, e.g. at the end of a piped script
+    engine.finish_search();
+}
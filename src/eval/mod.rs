@@ -0,0 +1,225 @@
+// =======================================================
+// Project: GatedChess
+// File: mod.rs
+// Description: Gate-aware static evaluation for bots and analysis.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+//! Scores a position in centipawns, split into terms so a bot can search on
+//! the total and a player can see where it comes from. Each term is White's
+//! share minus Black's, so positive numbers favour White.
+//!
+//! Gates are scored in three ways. A gate that will still stand after the
+//! next ply takes away the squares pieces could otherwise reach
+//! (`gate_denial`) and, next to a king, covers it (`king_shield`). A gate
+//! that goes away on the next ply only counts for half of both, since the
+//! side to move can wait it out (`expiring_gates`).
+
+use crate::config::BOARD_SIZE;
+use crate::game::bitboard::{Bitboard, Bitboards, square_bit, squares};
+use crate::game::{Game, Position};
+use crate::pieces::{Color, PieceType};
+
+/// Squares a piece can reach are worth this much each, per kind.
+fn mobility_weight(kind: PieceType) -> i32 {
+    match kind {
+        PieceType::Knight | PieceType::Bishop => 4,
+        PieceType::Rook => 2,
+        PieceType::Queen => 1,
+        PieceType::Pawn | PieceType::King => 0,
+    }
+}
+
+/// A gate next to a king blocks sliders and knights aimed at it.
+const KING_SHIELD_GATE: i32 = 6;
+
+pub fn piece_value(kind: PieceType) -> i32 {
+    match kind {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        PieceType::King => 0,
+    }
+}
+
+/// Centipawn score of a position by term, each from White's point of view.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Evaluation {
+    pub material: i32,
+    /// Advanced pawns and centralised minor pieces.
+    pub placement: i32,
+    /// Squares pieces would reach if there were no gates.
+    pub mobility: i32,
+    /// Mobility taken away by gates that outlast the next ply.
+    pub gate_denial: i32,
+    /// Gates that outlast the next ply standing next to a king.
+    pub king_shield: i32,
+    /// Half the denial and shield of gates that go away on the next ply.
+    pub expiring_gates: i32,
+}
+
+impl Evaluation {
+    pub fn total(&self) -> i32 {
+        self.material
+            + self.placement
+            + self.mobility
+            + self.gate_denial
+            + self.king_shield
+            + self.expiring_gates
+    }
+
+    /// The total from `color`'s point of view.
+    pub fn relative_to(&self, color: Color) -> i32 {
+        match color {
+            Color::White => self.total(),
+            Color::Black => -self.total(),
+        }
+    }
+
+    fn add_side(&mut self, side: Evaluation, color: Color) {
+        let sign = match color {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+        self.material += sign * side.material;
+        self.placement += sign * side.placement;
+        self.mobility += sign * side.mobility;
+        self.gate_denial += sign * side.gate_denial;
+        self.king_shield += sign * side.king_shield;
+        self.expiring_gates += sign * side.expiring_gates;
+    }
+}
+
+pub fn evaluate(game: &Game) -> Evaluation {
+    let expiring = game.bitboards().expiring;
+    let mut evaluation = Evaluation::default();
+    for color in [Color::White, Color::Black] {
+        evaluation.add_side(side_terms(game, color, expiring), color);
+    }
+    evaluation
+}
+
+fn side_terms(game: &Game, color: Color, expiring: Bitboard) -> Evaluation {
+    let actual = game.bitboards();
+    // The same position without any gates, and with only the lasting ones
+    let open = actual.keep_gates(0);
+    let lasting = actual.keep_gates(!expiring);
+
+    let mut terms = Evaluation::default();
+    let mut lasting_denial = 0;
+    let mut expiring_denial = 0;
+
+    for pos in squares(actual.colors[color as usize]) {
+        let Some(piece) = game.board[pos.row][pos.col].piece else {
+            continue;
+        };
+        terms.material += piece_value(piece.kind);
+        terms.placement += placement(piece.kind, color, pos);
+
+        let weight = mobility_weight(piece.kind);
+        if weight == 0 {
+            continue;
+        }
+        let reach = |bitboards: &Bitboards| {
+            bitboards.targets(pos, piece, game.en_passant).count_ones() as i32 * weight
+        };
+        let unblocked = reach(&open);
+        terms.mobility += unblocked;
+        if actual.gates != 0 {
+            let past_lasting = reach(&lasting);
+            lasting_denial += unblocked - past_lasting;
+            expiring_denial += past_lasting - reach(actual);
+        }
+    }
+
+    let (mut lasting_shield, mut expiring_shield) = (0, 0);
+    if let Some(king) = actual.king_square(color) {
+        let opponent = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        let zone = actual.gates_against(opponent) & king_zone(king);
+        lasting_shield = (zone & !expiring).count_ones() as i32 * KING_SHIELD_GATE;
+        expiring_shield = (zone & expiring).count_ones() as i32 * KING_SHIELD_GATE;
+    }
+
+    terms.gate_denial = -lasting_denial;
+    terms.king_shield = lasting_shield;
+    terms.expiring_gates = (expiring_shield - expiring_denial) / 2;
+    terms
+}
+
+/// Small bonuses for pawns that have advanced and minor pieces near the
+/// centre.
+fn placement(kind: PieceType, color: Color, pos: Position) -> i32 {
+    let centre = |i: usize| 3 - (2 * i as i32 - 7).abs() / 2;
+    match kind {
+        PieceType::Pawn => {
+            let advanced = match color {
+                Color::White => pos.row as i32 - 1,
+                Color::Black => 6 - pos.row as i32,
+            };
+            advanced * 6 + centre(pos.col) * 2
+        }
+        PieceType::Knight | PieceType::Bishop => (centre(pos.row) + centre(pos.col)) * 5,
+        _ => 0,
+    }
+}
+
+fn king_zone(king: Position) -> Bitboard {
+    let mut zone = 0;
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            let row = king.row as i32 + dr;
+            let col = king.col as i32 + dc;
+            if (dr, dc) != (0, 0)
+                && (0..BOARD_SIZE as i32).contains(&row)
+                && (0..BOARD_SIZE as i32).contains(&col)
+            {
+                zone |= square_bit(Position {
+                    row: row as usize,
+                    col: col as usize,
+                });
+            }
+        }
+    }
+    zone
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::fen::START_FEN;
+
+    #[test]
+    fn start_position_is_level() {
+        let game = Game::from_fen(START_FEN).unwrap();
+        assert_eq!(evaluate(&game).total(), 0);
+    }
+
+    #[test]
+    fn gates_deny_mobility_and_shield_kings() {
+        // A gate on d4 hems in White's bishop, one on g7 covers Black's king
+        let board = "6k1/8/8/8/8/8/8/B3K3 w - - 0 1";
+        let plain = evaluate(&Game::from_fen(board).unwrap());
+        let gated = evaluate(&Game::from_fen(&format!("{} d4:2,g7:2", board)).unwrap());
+        assert_eq!(plain.gate_denial, 0);
+        assert!(gated.gate_denial < 0);
+        assert_eq!(gated.king_shield, -KING_SHIELD_GATE);
+        assert_eq!(gated.mobility, plain.mobility);
+
+        // The same gates about to expire count for half
+        let expiring = evaluate(&Game::from_fen(&format!("{} d4:1,g7:1", board)).unwrap());
+        assert_eq!(expiring.gate_denial, 0);
+        assert_eq!(expiring.king_shield, 0);
+        assert_eq!(
+            expiring.expiring_gates,
+            (gated.gate_denial + gated.king_shield) / 2
+        );
+    }
+}
@@ -64,6 +64,17 @@ impl ChessClock {
         None
     }
 
+    pub fn remaining(&self, color: PieceColor) -> f64 {
+        match color {
+            PieceColor::White => self.white_remaining,
+            PieceColor::Black => self.black_remaining,
+        }
+    }
+
+    pub fn increment(&self) -> f64 {
+        self.increment_seconds
+    }
+
     pub fn apply_increment(&mut self, mover: PieceColor, now: f64) {
         match mover {
             PieceColor::White => self.white_remaining += self.increment_seconds,
@@ -107,6 +118,7 @@ impl ChessClock {
         );
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn draw_clock_panel(
         &self,
         x: f32,
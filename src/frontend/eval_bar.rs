@@ -0,0 +1,76 @@
+use macroquad::prelude::*;
+
+use crate::eval::Evaluation;
+use crate::pieces::Color as PieceColor;
+
+/// Centipawns at which the bar is three quarters full.
+const BAR_SCALE: f32 = 400.0;
+
+/// Draws a bar to the left of the board that fills with White's share of the
+/// evaluation. White's end sits at the bottom when the board is seen from
+/// White. Hovering the bar lists the terms behind the score.
+pub fn draw_eval_bar(evaluation: &Evaluation, perspective: PieceColor, tile_size: f32) {
+    let board_size = tile_size * 8.0;
+    let board_left = (screen_width() - board_size) / 2.0;
+    let board_top = (screen_height() - board_size) / 2.0;
+    let width = tile_size * 0.22;
+    let x = board_left - tile_size * 0.85;
+
+    let total = evaluation.total();
+    let white_share = 1.0 / (1.0 + 3f32.powf(-(total as f32) / BAR_SCALE));
+    let white_height = board_size * white_share;
+    let white_y = match perspective {
+        PieceColor::White => board_top + board_size - white_height,
+        PieceColor::Black => board_top,
+    };
+
+    draw_rectangle(x, board_top, width, board_size, Color::from_rgba(40, 40, 48, 255));
+    draw_rectangle(x, white_y, width, white_height, Color::from_rgba(235, 235, 230, 255));
+    draw_rectangle_lines(x, board_top, width, board_size, 2.0, GRAY);
+
+    // Score in pawns at the end of whoever is ahead
+    let label = format_pawns(total);
+    let font_size = tile_size * 0.22;
+    let label_w = measure_text(&label, None, font_size as u16, 1.0).width;
+    let white_end_at_bottom = perspective == PieceColor::White;
+    let at_bottom = (total >= 0) == white_end_at_bottom;
+    let label_y = if at_bottom {
+        board_top + board_size + font_size
+    } else {
+        board_top - font_size * 0.4
+    };
+    draw_text(&label, x + (width - label_w) / 2.0, label_y, font_size, WHITE);
+
+    let (mx, my) = mouse_position();
+    if mx >= x && mx <= x + width && my >= board_top && my <= board_top + board_size {
+        draw_breakdown(evaluation, x + width + 8.0, my);
+    }
+}
+
+fn draw_breakdown(evaluation: &Evaluation, x: f32, y: f32) {
+    let rows = [
+        ("Material", evaluation.material),
+        ("Placement", evaluation.placement),
+        ("Mobility", evaluation.mobility),
+        ("Gate denial", evaluation.gate_denial),
+        ("King shield", evaluation.king_shield),
+        ("Expiring gates", evaluation.expiring_gates),
+    ];
+    let row_h = 22.0;
+    let width = 200.0;
+    let height = row_h * rows.len() as f32 + 12.0;
+
+    draw_rectangle(x, y, width, height, Color::from_rgba(20, 20, 28, 230));
+    draw_rectangle_lines(x, y, width, height, 2.0, GOLD);
+    for (i, (name, score)) in rows.iter().enumerate() {
+        let row_y = y + 22.0 + i as f32 * row_h;
+        draw_text(name, x + 10.0, row_y, 20.0, LIGHTGRAY);
+        let value = format_pawns(*score);
+        let value_w = measure_text(&value, None, 20, 1.0).width;
+        draw_text(&value, x + width - value_w - 10.0, row_y, 20.0, WHITE);
+    }
+}
+
+fn format_pawns(centipawns: i32) -> String {
+    format!("{:+.1}", centipawns as f32 / 100.0)
+}
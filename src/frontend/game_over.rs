@@ -4,7 +4,7 @@ use macroquad::prelude::*;
 pub struct GameOverBanner;
 
 impl GameOverBanner {
-    pub fn draw(winner: Option<PieceColor>) -> bool {
+    pub fn draw(winner: Option<PieceColor>, draw_reason: Option<&str>) -> bool {
         let banner_width = 600.0;
         let banner_height = 200.0;
         let banner_x = (screen_width() - banner_width) / 2.0;
@@ -33,12 +33,20 @@ impl GameOverBanner {
         let title = "GAME OVER";
         draw_text(title, banner_x + 170.0, banner_y + 60.0, 48.0, WHITE);
 
-        let winner_text = match winner {
-            Some(PieceColor::White) => "White won.",
-            Some(PieceColor::Black) => "Black won.",
-            None => "Draw.",
+        let winner_text = match (winner, draw_reason) {
+            (Some(PieceColor::White), _) => "White won.".to_string(),
+            (Some(PieceColor::Black), _) => "Black won.".to_string(),
+            (None, Some(reason)) => format!("Draw by {}.", reason),
+            (None, None) => "Draw.".to_string(),
         };
-        draw_text(winner_text, banner_x + 200.0, banner_y + 120.0, 36.0, GOLD);
+        let winner_width = measure_text(&winner_text, None, 36, 1.0).width;
+        draw_text(
+            &winner_text,
+            banner_x + (banner_width - winner_width) / 2.0,
+            banner_y + 120.0,
+            36.0,
+            GOLD,
+        );
 
         draw_text(
             "Press ESC to exit",
@@ -0,0 +1,80 @@
+use macroquad::rand::gen_range;
+
+use crate::config::BOARD_SIZE;
+use crate::game::events::GameEvent;
+use crate::game::{Game, Position};
+
+/// Number of frames in the gate shimmer.
+const GATE_FRAMES: usize = 8;
+
+#[derive(Clone, Copy)]
+struct GateFrame {
+    frame: usize,
+    direction: i8,
+}
+
+/// Which frame of the shimmer each gate shows. The rules only know where
+/// gates stand, so the window keeps this alongside the game, following the
+/// game's gate events.
+pub struct GateAnimations {
+    squares: [[Option<GateFrame>; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl GateAnimations {
+    pub fn new() -> Self {
+        Self {
+            squares: [[None; BOARD_SIZE]; BOARD_SIZE],
+        }
+    }
+
+    /// Matches the gates on the board, for a new position or one a move was
+    /// taken back to.
+    pub fn sync(&mut self, game: &Game) {
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                let pos = Position { row, col };
+                if game.board[row][col].gate.is_none() {
+                    self.squares[row][col] = None;
+                } else if self.squares[row][col].is_none() {
+                    self.start(pos);
+                }
+            }
+        }
+    }
+
+    /// Follows a gate going up or coming down.
+    pub fn apply(&mut self, event: &GameEvent, game: &Game) {
+        match event {
+            GameEvent::GateSpawned { square, .. } => self.start(*square),
+            GameEvent::GateExpired { square } => self.squares[square.row][square.col] = None,
+            GameEvent::MoveTakenBack(_) => self.sync(game),
+            _ => {}
+        }
+    }
+
+    /// Starts a gate on a random frame, so neighbours don't pulse in step.
+    fn start(&mut self, pos: Position) {
+        self.squares[pos.row][pos.col] = Some(GateFrame {
+            frame: gen_range(0, GATE_FRAMES - 1),
+            direction: 1,
+        });
+    }
+
+    /// Moves every gate one frame on, turning back at either end.
+    pub fn advance(&mut self) {
+        let last = GATE_FRAMES as i8 - 1;
+        for gate in self.squares.iter_mut().flatten().flatten() {
+            let next = gate.frame as i8 + gate.direction;
+            gate.frame = next.clamp(0, last) as usize;
+            if next >= last {
+                gate.direction = -1;
+            } else if next <= 0 {
+                gate.direction = 1;
+            }
+        }
+    }
+
+    pub fn frame(&self, pos: Position) -> usize {
+        self.squares[pos.row][pos.col].map_or(0, |gate| gate.frame)
+    }
+}
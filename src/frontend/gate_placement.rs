@@ -0,0 +1,66 @@
+use macroquad::prelude::*;
+
+use crate::game::Position;
+
+/// Draws the button that turns gate placement on and off, level with the
+/// clocks at the right edge of the board. Returns true when it is clicked.
+pub fn draw_place_gate_button(stock: u8, placing: bool, enabled: bool, tile_size: f32) -> bool {
+    let board_size = tile_size * 8.0;
+    let board_right = (screen_width() + board_size) / 2.0;
+    let board_bottom = (screen_height() + board_size) / 2.0;
+    let width = tile_size * 2.2;
+    let height = tile_size * 0.62;
+    let x = board_right - width;
+    let y = board_bottom + tile_size * 0.12;
+
+    let hovered = enabled && is_hovered(x, y, width, height);
+    draw_rectangle(
+        x,
+        y,
+        width,
+        height,
+        if placing {
+            Color::from_rgba(90, 70, 30, 255)
+        } else if hovered {
+            Color::from_rgba(80, 80, 90, 255)
+        } else {
+            Color::from_rgba(40, 40, 50, 230)
+        },
+    );
+    draw_rectangle_lines(x, y, width, height, 2.0, if placing { GOLD } else { WHITE });
+
+    let label = if placing {
+        "Cancel Gate".to_string()
+    } else {
+        format!("Place Gate ({} left)", stock)
+    };
+    let font_size = tile_size * 0.2;
+    let label_w = measure_text(&label, None, font_size as u16, 1.0).width;
+    draw_text(
+        &label,
+        x + (width - label_w) / 2.0,
+        y + height * 0.6,
+        font_size,
+        if enabled { WHITE } else { DARKGRAY },
+    );
+
+    hovered && is_mouse_button_pressed(MouseButton::Left)
+}
+
+/// Marks the squares a gate may go on, in board space.
+pub fn draw_placement_squares(squares: &[Position], tile_size: f32) {
+    for pos in squares {
+        draw_rectangle(
+            pos.col as f32 * tile_size,
+            pos.row as f32 * tile_size,
+            tile_size,
+            tile_size,
+            Color::from_rgba(255, 200, 80, 60),
+        );
+    }
+}
+
+fn is_hovered(x: f32, y: f32, width: f32, height: f32) -> bool {
+    let (mx, my) = mouse_position();
+    mx >= x && mx <= x + width && my >= y && my <= y + height
+}
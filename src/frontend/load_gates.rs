@@ -1,5 +1,8 @@
 use macroquad::prelude::*;
 
+use crate::game::Position;
+use crate::gates::{Direction, GateType};
+
 pub struct GateTextures {
     pub tex_vector: Vec<Texture2D>,
 }
@@ -28,3 +31,66 @@ impl GateTextures {
         Self { tex_vector }
     }
 }
+
+impl GateTextures {
+    /// Draws `gate` on its tile in board space, where rows grow northwards.
+    /// Standard gates shimmer through `frame`. Walls hold still, darker and
+    /// framed, one-way gates carry an arrow the way they let pieces through,
+    /// and portals get a ring in a colour shared with their other end.
+    pub fn draw(&self, gate: GateType, pos: Position, frame: usize, size: f32, tint: Color) {
+        let x = pos.col as f32 * size;
+        let y = pos.row as f32 * size;
+        let (texture, tint) = match gate {
+            GateType::Wall { .. } => (
+                &self.tex_vector[0],
+                Color::new(tint.r * 0.55, tint.g * 0.55, tint.b * 0.55, 1.0),
+            ),
+            _ => (&self.tex_vector[frame], tint),
+        };
+        draw_texture_ex(
+            texture,
+            x,
+            y,
+            tint,
+            DrawTextureParams {
+                dest_size: Some(vec2(size, size)),
+                ..Default::default()
+            },
+        );
+
+        let centre = vec2(x + size / 2.0, y + size / 2.0);
+        match gate {
+            GateType::Standard { .. } => {}
+            GateType::Wall { .. } => {
+                draw_rectangle_lines(x + 2.0, y + 2.0, size - 4.0, size - 4.0, size * 0.08, DARKGRAY);
+            }
+            GateType::OneWay { toward, .. } => {
+                let ahead = match toward {
+                    Direction::North => vec2(0.0, 1.0),
+                    Direction::East => vec2(1.0, 0.0),
+                    Direction::South => vec2(0.0, -1.0),
+                    Direction::West => vec2(-1.0, 0.0),
+                };
+                let side = vec2(-ahead.y, ahead.x);
+                let tip = centre + ahead * size * 0.3;
+                let back = centre - ahead * size * 0.2;
+                draw_triangle(
+                    tip,
+                    back + side * size * 0.22,
+                    back - side * size * 0.22,
+                    Color::from_rgba(255, 255, 255, 210),
+                );
+            }
+            GateType::Portal { exit, .. } => {
+                let ring = portal_colour(pos.row * 8 + pos.col + exit.row * 8 + exit.col);
+                draw_circle_lines(centre.x, centre.y, size * 0.36, size * 0.08, ring);
+            }
+        }
+    }
+}
+
+/// Picks a ring colour from a number both ends of a portal share.
+fn portal_colour(pair: usize) -> Color {
+    const RINGS: [Color; 4] = [VIOLET, SKYBLUE, ORANGE, LIME];
+    RINGS[pair % RINGS.len()]
+}
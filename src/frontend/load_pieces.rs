@@ -1,9 +1,10 @@
-use crate::pieces::piece_animations::loaders::bishop_loader;
-use crate::pieces::piece_animations::loaders::king_loader;
-use crate::pieces::piece_animations::loaders::knight_loader;
-use crate::pieces::piece_animations::loaders::pawn_loader;
-use crate::pieces::piece_animations::loaders::queen_loader;
-use crate::pieces::piece_animations::loaders::rook_loader;
+use crate::frontend::piece_animations::loaders::bishop_loader;
+use crate::frontend::piece_animations::loaders::king_loader;
+use crate::frontend::piece_animations::loaders::knight_loader;
+use crate::frontend::piece_animations::loaders::pawn_loader;
+use crate::frontend::piece_animations::loaders::queen_loader;
+use crate::frontend::piece_animations::loaders::rook_loader;
+use crate::pieces::piece_fsm::PieceState;
 use crate::pieces::{Color, PieceType};
 use macroquad::prelude::*;
 use std::collections::HashMap;
@@ -15,9 +16,47 @@ pub enum AnimationState {
     Moving,
     Capturing,
     Selected,
+    Captured,
+    InCheck,
+    Promoted,
 }
 
-impl AnimationState {}
+/// Seconds a piece spends sliding to its new square.
+const MOVE_SECONDS: f32 = 0.3;
+/// Seconds a knight spends on its hop, a little longer than a slide.
+const KNIGHT_MOVE_SECONDS: f32 = 0.4;
+/// Seconds a capturing piece stays in its strike.
+const CAPTURE_SECONDS: f32 = 0.45;
+/// Seconds a freshly promoted piece shows off.
+const PROMOTION_SECONDS: f32 = 0.8;
+
+impl AnimationState {
+    /// How long a `kind` plays this state before settling back to idle, or
+    /// `None` if it holds until the game moves on.
+    pub fn duration(self, kind: PieceType) -> Option<f32> {
+        match self {
+            AnimationState::Moving if kind == PieceType::Knight => Some(KNIGHT_MOVE_SECONDS),
+            AnimationState::Moving => Some(MOVE_SECONDS),
+            AnimationState::Capturing => Some(CAPTURE_SECONDS),
+            AnimationState::Promoted => Some(PROMOTION_SECONDS),
+            _ => None,
+        }
+    }
+}
+
+impl From<PieceState> for AnimationState {
+    fn from(state: PieceState) -> Self {
+        match state {
+            PieceState::Idle => AnimationState::Idle,
+            PieceState::Selected => AnimationState::Selected,
+            PieceState::Moving => AnimationState::Moving,
+            PieceState::Capturing => AnimationState::Capturing,
+            PieceState::Captured => AnimationState::Captured,
+            PieceState::InCheck => AnimationState::InCheck,
+            PieceState::Promoted => AnimationState::Promoted,
+        }
+    }
+}
 
 impl From<&str> for AnimationState {
     fn from(s: &str) -> Self {
@@ -26,6 +65,9 @@ impl From<&str> for AnimationState {
             "moving" => AnimationState::Moving,
             "capturing" => AnimationState::Capturing,
             "selected" => AnimationState::Selected,
+            "captured" => AnimationState::Captured,
+            "in_check" => AnimationState::InCheck,
+            "promoted" => AnimationState::Promoted,
             _ => AnimationState::Idle,
         }
     }
@@ -1,83 +1,107 @@
+use crate::ai::ComputerPlayer;
 use crate::board::create_board;
+use crate::eval::evaluate;
 use crate::frontend::load_pieces::AnimationState;
+use crate::game::events::GameEvent;
 use crate::game::moves::generation::get_piece_moves;
-use crate::game::{Game, Position};
-use crate::gates::update_gate_animation;
-use crate::gates::update_gates;
+use crate::game::{Game, GameResult, Move, MoveError, Position};
 use crate::network::{NetworkCommand, NetworkEvent, OnlineSession, SessionConfig, SessionRole};
+use crate::parser::algebraic::parse_move;
+use crate::parser::pgn::{PgnTags, export_pgn};
+use crate::parser::san::{game_san, parse_san, to_san};
 use crate::pieces::Color as PieceColor;
 use crate::pieces::Color::{Black, White};
 use crate::pieces::Piece;
+use crate::pieces::piece_fsm::PieceState;
 use macroquad::prelude::*;
 
 mod chess_clock;
+mod eval_bar;
 mod game_over;
+mod gate_animation;
+mod gate_placement;
 mod load_frame;
 mod load_gates;
 mod load_pieces;
 mod move_history;
+mod piece_animations;
+mod promotion_picker;
 mod session_banner;
 mod start_menu;
 
 use chess_clock::ChessClock;
+use eval_bar::draw_eval_bar;
 use game_over::GameOverBanner;
+use gate_animation::GateAnimations;
+use gate_placement::{draw_place_gate_button, draw_placement_squares};
 use load_frame::BoardFrame;
 use load_gates::GateTextures;
 use load_pieces::PieceTextures;
 use move_history::MoveHistory;
+use promotion_picker::PromotionPicker;
 use session_banner::draw_status;
 use start_menu::StartMenu;
+pub use start_menu::StartPosition;
 
 static mut SELECTED: Option<Position> = None;
 static mut HOVERED: Option<Position> = None;
 static mut HIGHLIGHTED_COLUMN: Option<usize> = None;
 static mut TYPING_MODE: bool = false;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-struct AutoMove {
-    from: Position,
-    to: Position,
-}
-
-// Add animation state tracking
-struct PieceAnimationState {
-    current_frame: usize,
-    last_update: f32,
-    frame_duration: f32, // seconds per frame
-    direction: i32,
-}
+/// Seconds each frame of a piece animation stays up.
+const PIECE_FRAME_SECONDS: f32 = 0.3;
 
-impl PieceAnimationState {
-    fn new(frame_duration: f32) -> Self {
-        Self {
-            current_frame: 0,
-            last_update: 0.0,
-            frame_duration,
-            direction: 1,
-        }
+/// The frame a piece shows after `elapsed` seconds in its state, running
+/// through the frames and back again.
+fn piece_frame(elapsed: f32, total_frames: usize) -> usize {
+    if total_frames < 2 {
+        return 0;
     }
+    let period = 2 * (total_frames - 1);
+    let step = (elapsed / PIECE_FRAME_SECONDS) as usize % period;
+    if step < total_frames {
+        step
+    } else {
+        period - step
+    }
+}
 
-    fn update(&mut self, current_time: f32, total_frames: usize) {
-        if total_frames == 0 {
-            return;
-        }
-
-        if current_time - self.last_update >= self.frame_duration {
-            let next = self.current_frame as i32 + self.direction;
+/// The frames and tint for a piece in the state its FSM is in. States
+/// without frames of their own borrow the idle ones.
+fn piece_texture<'a>(
+    game: &Game,
+    textures: &'a PieceTextures,
+    piece: Piece,
+    animations_enabled: bool,
+) -> Option<(&'a Texture2D, Color)> {
+    let (state, elapsed) = game
+        .state_manager
+        .fsm(piece.id)
+        .map_or((PieceState::Idle, 0.0), |fsm| {
+            (fsm.state(), fsm.time_in_state())
+        });
 
-            if next >= total_frames as i32 {
-                self.direction = -1;
-                self.current_frame = total_frames.saturating_sub(2).max(0);
-            } else if next < 0 {
-                self.direction = 1;
-                self.current_frame = 1.min(total_frames - 1);
-            } else {
-                self.current_frame = next as usize;
-            }
+    let frames = textures
+        .get_animation(piece.kind, piece.color, state.into())
+        .or_else(|| textures.get_animation(piece.kind, piece.color, AnimationState::Idle));
+    let tex = match frames {
+        Some(frames) if animations_enabled => &frames[piece_frame(elapsed, frames.len())],
+        Some(frames) => &frames[0],
+        None => textures.get(piece.kind, piece.color)?,
+    };
 
-            self.last_update = current_time;
-        }
-    }
+    let pulse = if animations_enabled {
+        0.5 + 0.5 * (elapsed * 6.0).sin()
+    } else {
+        1.0
+    };
+    let tint = match state {
+        PieceState::Selected => Color::new(1.0, 0.95, 0.7, 1.0),
+        PieceState::InCheck => Color::new(1.0, 0.55 + 0.3 * pulse, 0.55 + 0.3 * pulse, 1.0),
+        PieceState::Promoted => Color::new(1.0, 0.85 + 0.15 * pulse, 0.5 + 0.5 * pulse, 1.0),
+        _ => WHITE,
+    };
+    Some((tex, tint))
 }
 
 struct PieceSnapAnimation {
@@ -107,7 +131,7 @@ impl PieceSnapAnimation {
     }
 }
 
-pub async fn run_ui() {
+pub async fn run_ui(start_position: Option<StartPosition>) {
     let board_frame = BoardFrame::load("images/frames/frame-1.png").await;
     let piece_textures = PieceTextures::load().await;
     let gate_textures = GateTextures::load().await;
@@ -115,8 +139,6 @@ pub async fn run_ui() {
     let dark_tile = load_texture("images/panel/black-panel.png").await.unwrap();
 
     'main: loop {
-        let board = create_board();
-        let mut game = Game::new(board);
         unsafe {
             SELECTED = None;
             HOVERED = None;
@@ -133,47 +155,103 @@ pub async fn run_ui() {
             next_frame().await;
         };
 
+        // The menu only offers positions that load
+        let mut game = Game::from_fen(&launch_config.start_fen)
+            .unwrap_or_else(|_| Game::new(create_board(), launch_config.gate_rules));
+        game.set_gate_rules(launch_config.gate_rules);
+        let mut move_history = MoveHistory::new();
+        let mut start_warning = None;
+        let offline = matches!(
+            launch_config.session,
+            SessionConfig::Local | SessionConfig::VsComputer { .. }
+        );
+        if let (true, Some(start)) = (offline, &start_position) {
+            match start.load() {
+                Ok(loaded) => {
+                    game = loaded;
+                    game.set_gate_rules(launch_config.gate_rules);
+                    for san in game_san(&game) {
+                        move_history.add_move(san);
+                    }
+                }
+                Err(err) => start_warning = Some(format!("Ignoring start position: {}", err)),
+            }
+        }
+
         let mut last_turn = game.current_turn;
         let mut last_update = 0.0;
-        let mut move_history = MoveHistory::new();
+        let mut gate_animations = GateAnimations::new();
+        // Replaying a loaded game queued events nobody needs
+        game.drain_events();
+        gate_animations.sync(&game);
         let session = match &launch_config.session {
-            SessionConfig::Local => None,
+            SessionConfig::Local | SessionConfig::VsComputer { .. } => None,
             SessionConfig::Host { bind_addr } => Some(OnlineSession::host(
                 bind_addr.clone(),
                 launch_config.time_control,
+                launch_config.gate_rules,
+                launch_config.start_fen.clone(),
             )),
             SessionConfig::Join { server_addr } => Some(OnlineSession::join(server_addr.clone())),
             SessionConfig::FindMatch { addr } => Some(OnlineSession::find_match(
                 addr.clone(),
                 launch_config.time_control,
+                launch_config.gate_rules,
+                launch_config.start_fen.clone(),
             )),
         };
+        let mut computer = match &launch_config.session {
+            SessionConfig::VsComputer { strength } => Some(ComputerPlayer::new(Black, *strength)),
+            _ => None,
+        };
         let mut clock = ChessClock::new(launch_config.time_control, get_time());
         let mut status_message = match &launch_config.session {
             SessionConfig::Local => None,
             SessionConfig::Host { bind_addr } => Some(format!("Hosting on {}", bind_addr)),
             SessionConfig::Join { server_addr } => Some(format!("Connecting to {}", server_addr)),
             SessionConfig::FindMatch { addr } => Some(format!("Searching for match at {}", addr)),
+            SessionConfig::VsComputer { strength } => {
+                Some(format!("Playing the computer ({})", strength.label()))
+            }
         };
         let mut status_visible = status_message.is_some();
         let mut status_expire_turn: Option<u32> = status_message.as_ref().map(|_| 1);
         let mut status_started_at = status_message.as_ref().map(|_| get_time());
-        let mut connection_ready = matches!(launch_config.session, SessionConfig::Local);
+        let mut connection_ready = offline;
 
         let mut game_over = false;
+        // Forfeits and flag falls can't be taken back
+        let mut game_conceded = false;
         let mut game_over_banner_visible = true;
         let mut winner: Option<crate::pieces::Color> = None;
         let mut turn_count: u32 = 0;
-        let mut queued_auto_move: Option<AutoMove> = None;
+        if let Some(warning) = &start_warning {
+            set_status_message(
+                &mut status_message,
+                &mut status_visible,
+                &mut status_expire_turn,
+                &mut status_started_at,
+                warning,
+                turn_count,
+            );
+        }
+        let mut queued_auto_move: Option<Move> = None;
+        let mut pending_promotion: Option<Move> = None;
+        // Clicks on the board place a gate instead of picking pieces
+        let mut placing_gate = false;
+        let mut move_input = String::new();
 
-        let mut piece_anim_state = PieceAnimationState::new(0.3);
         let mut animations_enabled = true;
+        let mut eval_bar_enabled = true;
         let mut gear_open = false;
         let mut snap_anim: Option<PieceSnapAnimation> = None;
 
     loop {
         let now = get_time();
-        let board_perspective = board_perspective(&session, game.current_turn);
+        let board_perspective = match &computer {
+            Some(bot) => opponent(bot.color()),
+            None => board_perspective(&session, game.current_turn),
+        };
 
         while let Some(event) = session.as_ref().and_then(|online| online.try_recv()) {
             match event {
@@ -220,7 +298,20 @@ pub async fn run_ui() {
                         turn_count,
                     );
                 }
-                NetworkEvent::RemoteMove(from, to) => {
+                NetworkEvent::GateRulesUpdated(gate_rules) => {
+                    game.set_gate_rules(gate_rules);
+                }
+                NetworkEvent::StartPositionUpdated(fen) => {
+                    // Arrives before any move, right after the gate rules
+                    if let Ok(mut start) = Game::from_fen(&fen) {
+                        start.set_gate_rules(game.gate_rules());
+                        game = start;
+                        game.drain_events();
+                        gate_animations.sync(&game);
+                        last_turn = game.current_turn;
+                    }
+                }
+                NetworkEvent::RemoteMove(mv) => {
                     let is_host = session
                         .as_ref()
                         .map(|online| online.role() == SessionRole::Host)
@@ -228,11 +319,11 @@ pub async fn run_ui() {
 
                     if is_host {
                         let mover = game.current_turn;
-                        if game.make_move(from, to).is_ok() {
+                        if let Ok(played) = play_move(&mut game, &mut move_history, mv) {
+                            let (from, to) = (played.from, played.landing());
                             if let Some(piece) = game.board[to.row][to.col].piece {
                                 snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
                             }
-                            move_history.add_move(from, to);
                             clock.apply_increment(mover, now);
                             set_status_message(
                                 &mut status_message,
@@ -244,7 +335,7 @@ pub async fn run_ui() {
                             );
 
                             if let Some(online) = &session {
-                                online.send(NetworkCommand::BroadcastMove(from, to));
+                                online.send(NetworkCommand::BroadcastMove(played));
                             }
 
                             update_game_over_state(&game, &mut game_over, &mut winner);
@@ -254,11 +345,11 @@ pub async fn run_ui() {
                         }
                     } else {
                         let mover = game.current_turn;
-                        if game.make_move(from, to).is_ok() {
+                        if let Ok(played) = play_move(&mut game, &mut move_history, mv) {
+                            let (from, to) = (played.from, played.landing());
                             if let Some(piece) = game.board[to.row][to.col].piece {
                                 snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
                             }
-                            move_history.add_move(from, to);
                             clock.apply_increment(mover, now);
                             set_status_message(
                                 &mut status_message,
@@ -303,6 +394,7 @@ pub async fn run_ui() {
             clock.update(game.current_turn, now, !game_over && connection_ready)
         {
             game_over = true;
+            game_conceded = true;
             game_over_banner_visible = true;
             winner = Some(timeout_winner);
             set_status_message(
@@ -325,7 +417,6 @@ pub async fn run_ui() {
         if !game_over {
             if game.current_turn != last_turn {
                 turn_count += 1;
-                update_gates(&mut game);
                 last_turn = game.current_turn;
 
                 if matches!(status_expire_turn, Some(expire_at) if turn_count >= expire_at) {
@@ -334,7 +425,7 @@ pub async fn run_ui() {
             }
 
             if now - last_update >= 0.5 {
-                update_gate_animation(&mut game);
+                gate_animations.advance();
                 last_update = now;
             }
         }
@@ -379,35 +470,56 @@ pub async fn run_ui() {
         board_frame.draw(tile_size);
         draw_board(
             &game,
+            &gate_animations,
             &light_tile,
             &dark_tile,
-            &gate_textures.tex_vector,
+            &gate_textures,
             tile_size,
         );
 
         // Draw highlighted column before pieces
         draw_highlighted_column(tile_size);
-        draw_queued_move(tile_size, queued_auto_move);
+        if placing_gate {
+            draw_placement_squares(&game.gate_placements(game.current_turn), tile_size);
+        }
+        draw_queued_move(tile_size, queued_auto_move.as_ref());
 
         // Expire completed snap animation before drawing
         if snap_anim.as_ref().map(|a| a.is_complete(now as f32)).unwrap_or(false) {
             snap_anim = None;
         }
 
+        // Pieces follow the selection and the clock; moves reach them
+        // through the game's events
+        let held = unsafe { SELECTED }
+            .and_then(|pos| game.board[pos.row][pos.col].piece)
+            .map(|piece| piece.id);
+        game.state_manager.select(held);
+        game.state_manager.update(get_frame_time(), |kind, state| {
+            AnimationState::from(state).duration(kind)
+        });
+
         let snap_skip = snap_anim.as_ref().map(|a| a.to);
         draw_pieces(
             &game,
             &piece_textures,
             &camera,
             tile_size,
-            &mut piece_anim_state,
-            now as f32,
             board_perspective,
             animations_enabled,
             snap_skip,
         );
         if let Some(anim) = &snap_anim {
-            draw_snap_anim(anim, &piece_textures, tile_size, &camera, board_perspective, now as f32, animations_enabled, &mut piece_anim_state);
+            draw_snap_anim(
+                &game,
+                anim,
+                &piece_textures,
+                tile_size,
+                &camera,
+                board_perspective,
+                now as f32,
+                animations_enabled,
+            );
         }
         draw_selected(tile_size);
 
@@ -415,101 +527,120 @@ pub async fn run_ui() {
 
         // Draw row numbers after resetting camera (so they're not affected by board rotation)
         draw_row_numbers(board_perspective, tile_size);
+        draw_move_input(&move_input, tile_size);
         clock.draw(game.current_turn, tile_size);
+        // Only offline, so it can't help anyone in an online game
+        if eval_bar_enabled && session.is_none() {
+            draw_eval_bar(&evaluate(&game), board_perspective, tile_size);
+        }
 
         // Process clicks only if game is not over and the gear panel didn't consume the click
-        let local_turn = can_interact(&session, connection_ready, game.current_turn);
+        let local_turn = can_interact(&session, connection_ready, game.current_turn)
+            && !is_computer_turn(&computer, game.current_turn);
         let can_queue_auto_move =
             can_queue_auto_move(&session, connection_ready, game.current_turn);
         let gear_hit = gear_panel_hit(gear_open);
 
-        if !game_over && local_turn && !gear_hit {
-            if let Some(auto_move) = queued_auto_move {
-                let local_color = local_player_color(&session, game.current_turn);
-
-                if is_move_legal_for_color(&game, auto_move.from, auto_move.to, local_color) {
-                    try_local_move(
-                        &mut game,
-                        &mut move_history,
-                        &mut clock,
-                        &session,
-                        &mut game_over,
-                        &mut game_over_banner_visible,
-                        &mut winner,
-                        &mut status_message,
-                        &mut status_visible,
-                        &mut status_expire_turn,
-                        &mut status_started_at,
-                        turn_count,
-                        auto_move.from,
-                        auto_move.to,
-                    );
-                    queued_auto_move = None;
-                } else {
-                    queued_auto_move = None;
-                    set_status_message(
-                        &mut status_message,
-                        &mut status_visible,
-                        &mut status_expire_turn,
-                        &mut status_started_at,
-                        "Queued move is no longer legal",
-                        turn_count,
-                    );
+        // Only games with a stock of gates to place get the button
+        if !local_turn || game_over {
+            placing_gate = false;
+        }
+        if game.gate_rules().placement_stock > 0 {
+            let stock = game.gate_stock(game.current_turn);
+            let enabled = !game_over && local_turn && pending_promotion.is_none() && stock > 0;
+            if draw_place_gate_button(stock, placing_gate, enabled, tile_size) && enabled {
+                placing_gate = !placing_gate;
+                unsafe {
+                    SELECTED = None;
                 }
             }
         }
 
+        if !game_over
+            && local_turn
+            && !gear_hit
+            && let Some(auto_move) = queued_auto_move.take()
+        {
+            let local_color = local_player_color(&session, game.current_turn);
+
+            if is_move_legal_for_color(&game, &auto_move, local_color) {
+                try_local_move(
+                    &mut game,
+                    &mut move_history,
+                    &mut clock,
+                    &session,
+                    &mut game_over,
+                    &mut game_over_banner_visible,
+                    &mut winner,
+                    &mut status_message,
+                    &mut status_visible,
+                    &mut status_expire_turn,
+                    &mut status_started_at,
+                    turn_count,
+                    auto_move,
+                );
+            } else {
+                set_status_message(
+                    &mut status_message,
+                    &mut status_visible,
+                    &mut status_expire_turn,
+                    &mut status_started_at,
+                    "Queued move is no longer legal",
+                    turn_count,
+                );
+            }
+        }
+
         if !game_over && (local_turn || can_queue_auto_move) {
-            // Process keyboard input first
-            if let Some((from, to)) = process_keyboard_input(&game) {
-                if local_turn {
-                    let pre_piece = game.board[from.row][from.col].piece;
-                    try_local_move(
-                        &mut game,
-                        &mut move_history,
-                        &mut clock,
-                        &session,
-                        &mut game_over,
-                        &mut game_over_banner_visible,
-                        &mut winner,
-                        &mut status_message,
-                        &mut status_visible,
-                        &mut status_expire_turn,
-                        &mut status_started_at,
-                        turn_count,
-                        from,
-                        to,
-                    );
-                    if pre_piece.is_some() && game.board[from.row][from.col].piece.is_none() {
-                        if let Some(piece) = game.board[to.row][to.col].piece {
-                            snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
-                        }
+            let mut submitted: Option<Move> = None;
+
+            if let Some(mv) = &pending_promotion {
+                // The picker takes over input until a piece is chosen
+                let color = local_player_color(&session, game.current_turn);
+                if let Some(kind) = PromotionPicker::draw(color, &piece_textures) {
+                    submitted = Some(mv.clone().with_promotion(Some(kind)));
+                    pending_promotion = None;
+                } else if is_key_pressed(KeyCode::Escape) {
+                    pending_promotion = None;
+                }
+            } else if placing_gate {
+                if is_key_pressed(KeyCode::Escape) {
+                    placing_gate = false;
+                } else if let Some(square) = clicked_square(&camera, tile_size) {
+                    submitted = Some(Move::place_gate(square));
+                    placing_gate = false;
+                }
+            } else {
+                // Process keyboard input first
+                if let Some(mv) = process_keyboard_input(&game, &mut move_input) {
+                    submitted = Some(mv);
+                    unsafe {
+                        SELECTED = None;
+                        HIGHLIGHTED_COLUMN = None;
+                        TYPING_MODE = false;
                     }
-                } else {
-                    update_auto_move(
-                        &game,
-                        &session,
-                        &mut queued_auto_move,
-                        &mut status_message,
-                        &mut status_visible,
-                        &mut status_expire_turn,
-                        &mut status_started_at,
-                        turn_count,
-                        from,
-                        to,
-                    );
                 }
-                unsafe {
-                    SELECTED = None;
-                    HIGHLIGHTED_COLUMN = None;
-                    TYPING_MODE = false;
+
+                // Then process mouse clicks
+                if !gear_hit && let Some(mv) = process_click(&game, &camera, tile_size) {
+                    submitted = Some(mv);
+                    unsafe {
+                        SELECTED = None;
+                    }
                 }
             }
 
-            // Then process mouse clicks
-            if !gear_hit {
-            if let Some((from, to)) = process_click(&game, &camera, tile_size) {
-                if local_turn {
+            if let Some(mv) = submitted {
+                let (from, to) = (mv.from, mv.to);
+                let local_color = local_player_color(&session, game.current_turn);
+
+                if mv.promotion.is_none()
+                    && game.is_promotion(from, to)
+                    && is_move_legal_for_color(&game, &mv, local_color)
+                {
+                    // Ask which piece to promote to before submitting the move
+                    pending_promotion = Some(mv);
+                } else if local_turn {
                     let pre_piece = game.board[from.row][from.col].piece;
                     try_local_move(
                         &mut game,
@@ -524,13 +655,17 @@ pub async fn run_ui() {
                         &mut status_expire_turn,
                         &mut status_started_at,
                         turn_count,
-                        from,
-                        to,
+                        mv,
                     );
-                    if pre_piece.is_some() && game.board[from.row][from.col].piece.is_none() {
-                        if let Some(piece) = game.board[to.row][to.col].piece {
-                            snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
-                        }
+                    // A castling king lands beside the rook it was moved onto
+                    let landed = game
+                        .last_move()
+                        .filter(|played| (played.from, played.to) == (from, to))
+                        .map(Move::landing);
+                    if let (Some(_), Some(to)) = (pre_piece, landed)
+                        && let Some(piece) = game.board[to.row][to.col].piece
+                    {
+                        snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
                     }
                 } else {
                     update_auto_move(
@@ -542,15 +677,53 @@ pub async fn run_ui() {
                         &mut status_expire_turn,
                         &mut status_started_at,
                         turn_count,
-                        from,
-                        to,
+                        mv,
                     );
                 }
-                unsafe {
-                    SELECTED = None;
+            }
+        }
+
+        // The computer thinks on its own thread and moves once it has an answer
+        if let Some(bot) = computer.as_mut()
+            && !game_over
+            && bot.color() == game.current_turn
+        {
+            if !bot.is_thinking() {
+                let limits = bot
+                    .strength()
+                    .limits(clock.remaining(bot.color()), clock.increment());
+                bot.start_thinking(&game, limits);
+            } else if let Some(mv) = bot.try_recv() {
+                let mover = game.current_turn;
+                if let Ok(played) = play_move(&mut game, &mut move_history, mv) {
+                    let (from, to) = (played.from, played.landing());
+                    if let Some(piece) = game.board[to.row][to.col].piece {
+                        snap_anim = Some(PieceSnapAnimation::new(from, to, piece, now as f32));
+                    }
+                    clock.apply_increment(mover, now);
+                    update_game_over_state(&game, &mut game_over, &mut winner);
+                    game_over_banner_visible = true;
+                }
+            }
+        }
+
+        let local_color = local_player_color(&session, game.current_turn);
+        for event in game.drain_events() {
+            gate_animations.apply(&event, &game);
+            // Only the player who tried the move needs telling
+            if let GameEvent::MoveBlockedByGate { from, gate, .. } = event {
+                if game.board[from.row][from.col].piece.is_none_or(|p| p.color != local_color) {
+                    continue;
                 }
+                set_status_message(
+                    &mut status_message,
+                    &mut status_visible,
+                    &mut status_expire_turn,
+                    &mut status_started_at,
+                    &format!("Blocked by the gate on {}", gate.to_algebraic()),
+                    turn_count,
+                );
             }
-            } // end !gear_hit
         }
 
         // Draw move history panel
@@ -558,7 +731,7 @@ pub async fn run_ui() {
 
         // Draw game over banner if game ended
         if game_over && game_over_banner_visible {
-            if GameOverBanner::draw(winner) {
+            if GameOverBanner::draw(winner, draw_reason(&game.result)) {
                 game_over_banner_visible = false;
             }
 
@@ -567,26 +740,98 @@ pub async fn run_ui() {
             }
         }
 
-        if status_visible {
-            if let Some(message) = &status_message {
-                let title = if connection_ready {
-                    "Online Play"
-                } else {
-                    "Connection"
-                };
-                let started_at = status_started_at.unwrap_or(now);
-                let visible_chars =
-                    (((now - started_at) * 36.0).floor() as usize).min(message.chars().count());
-                if draw_status(title, message, visible_chars) {
-                    status_visible = false;
-                }
+        if status_visible && let Some(message) = &status_message {
+            let title = if computer.is_some() {
+                "Computer"
+            } else if connection_ready {
+                "Online Play"
+            } else {
+                "Connection"
+            };
+            let started_at = status_started_at.unwrap_or(now);
+            let visible_chars =
+                (((now - started_at) * 36.0).floor() as usize).min(message.chars().count());
+            if draw_status(title, message, visible_chars) {
+                status_visible = false;
             }
         }
 
-        match draw_gear_panel(&mut gear_open, &mut animations_enabled, !game_over) {
+        let takebacks_allowed = session.is_none() && !game_conceded;
+        match draw_gear_panel(
+            &mut gear_open,
+            &mut animations_enabled,
+            &mut eval_bar_enabled,
+            session.is_none(),
+            !game_over,
+            takebacks_allowed && game.can_undo(),
+            takebacks_allowed && game.can_redo(),
+        ) {
             GearAction::MainMenu => continue 'main,
+            GearAction::CopyFen => {
+                miniquad::window::clipboard_set(&game.to_fen());
+                gear_open = false;
+            }
+            GearAction::CopyPgn => {
+                let tags = PgnTags {
+                    event: "Gated Chess game".to_string(),
+                    black: match &computer {
+                        Some(bot) => format!("Computer ({})", bot.strength().label()),
+                        None => "?".to_string(),
+                    },
+                    result: conceded_result(game_conceded, winner),
+                    time_control: Some(launch_config.time_control),
+                    ..PgnTags::default()
+                };
+                miniquad::window::clipboard_set(&export_pgn(&game, &tags));
+                gear_open = false;
+            }
+            GearAction::TakeBack => {
+                if let Some(bot) = computer.as_mut() {
+                    bot.cancel();
+                }
+                let mut taken_back = false;
+                while game.unmake_move().is_some() {
+                    move_history.pop_move();
+                    taken_back = true;
+                    // Against the computer, go back to the player's own move
+                    if !is_computer_turn(&computer, game.current_turn) {
+                        break;
+                    }
+                }
+                if taken_back {
+                    // Taking a move back doesn't count as a new turn
+                    last_turn = game.current_turn;
+                    queued_auto_move = None;
+                    pending_promotion = None;
+                    placing_gate = false;
+                    unsafe {
+                        SELECTED = None;
+                    }
+                    game_over = false;
+                    game_over_banner_visible = true;
+                    winner = None;
+                    update_game_over_state(&game, &mut game_over, &mut winner);
+                }
+            }
+            GearAction::Redo => {
+                if let Some(bot) = computer.as_mut() {
+                    bot.cancel();
+                }
+                let san = game.next_redo().map(|mv| to_san(&game, mv));
+                if let (Some(san), Some(_)) = (san, game.redo_move()) {
+                    move_history.add_move(san);
+                    queued_auto_move = None;
+                    pending_promotion = None;
+                    placing_gate = false;
+                    unsafe {
+                        SELECTED = None;
+                    }
+                    update_game_over_state(&game, &mut game_over, &mut winner);
+                }
+            }
             GearAction::Forfeit => {
                 game_over = true;
+                game_conceded = true;
                 game_over_banner_visible = true;
                 winner = Some(if game.current_turn == White { Black } else { White });
                 gear_open = false;
@@ -601,21 +846,27 @@ pub async fn run_ui() {
 
 fn draw_board(
     game: &Game,
+    gate_animations: &GateAnimations,
     light: &Texture2D,
     dark: &Texture2D,
-    gates: &[Texture2D],
+    gates: &GateTextures,
     tile_size: f32,
 ) {
+    // Gates only need telling apart when they let their owner through
+    let owned = !game.gate_rules().blocks_owner;
     for row in 0..8 {
         for col in 0..8 {
-            let tex: &Texture2D = if game.board[row][col].gate.is_some() {
-                let frame = game.board[row][col].animation_frame.unwrap_or(0);
-                &gates[frame]
-            } else if (row + col) % 2 == 0 {
-                light
-            } else {
-                dark
-            };
+            let pos = Position { row, col };
+            if let Some(gate) = game.board[row][col].gate {
+                let tint = if owned {
+                    gate_tint(gate.owner())
+                } else {
+                    WHITE
+                };
+                gates.draw(gate, pos, gate_animations.frame(pos), tile_size, tint);
+                continue;
+            }
+            let tex = if (row + col) % 2 == 0 { light } else { dark };
 
             draw_texture_ex(
                 tex,
@@ -631,9 +882,21 @@ fn draw_board(
     }
 }
 
+/// Warm for White's gates, cool for Black's.
+fn gate_tint(owner: PieceColor) -> Color {
+    match owner {
+        White => Color::from_rgba(255, 226, 170, 255),
+        Black => Color::from_rgba(150, 170, 255, 255),
+    }
+}
+
 enum GearAction {
     None,
     MainMenu,
+    CopyFen,
+    CopyPgn,
+    TakeBack,
+    Redo,
     Forfeit,
 }
 
@@ -642,28 +905,39 @@ const GEAR_Y: f32 = 12.0;
 const GEAR_SIZE: f32 = 36.0;
 const PANEL_W: f32 = 180.0;
 const PANEL_ITEM_H: f32 = 40.0;
+const PANEL_ITEMS: f32 = 8.0;
 
 fn gear_panel_hit(open: bool) -> bool {
     let (mx, my) = mouse_position();
     if !is_mouse_button_pressed(MouseButton::Left) {
         return false;
     }
-    let btn_hit = mx >= GEAR_X && mx <= GEAR_X + GEAR_SIZE && my >= GEAR_Y && my <= GEAR_Y + GEAR_SIZE;
+    let btn_hit =
+        (GEAR_X..=GEAR_X + GEAR_SIZE).contains(&mx) && (GEAR_Y..=GEAR_Y + GEAR_SIZE).contains(&my);
     if btn_hit {
         return true;
     }
     if open {
         let panel_y = GEAR_Y + GEAR_SIZE + 4.0;
-        let panel_h = PANEL_ITEM_H * 3.0 + 8.0;
-        mx >= GEAR_X && mx <= GEAR_X + PANEL_W && my >= panel_y && my <= panel_y + panel_h
+        let panel_h = PANEL_ITEM_H * PANEL_ITEMS + 8.0;
+        (GEAR_X..=GEAR_X + PANEL_W).contains(&mx) && (panel_y..=panel_y + panel_h).contains(&my)
     } else {
         false
     }
 }
 
-fn draw_gear_panel(open: &mut bool, animations_enabled: &mut bool, can_forfeit: bool) -> GearAction {
+fn draw_gear_panel(
+    open: &mut bool,
+    animations_enabled: &mut bool,
+    eval_bar_enabled: &mut bool,
+    can_show_eval: bool,
+    can_forfeit: bool,
+    can_take_back: bool,
+    can_redo: bool,
+) -> GearAction {
     let (mx, my) = mouse_position();
-    let btn_hovered = mx >= GEAR_X && mx <= GEAR_X + GEAR_SIZE && my >= GEAR_Y && my <= GEAR_Y + GEAR_SIZE;
+    let btn_hovered =
+        (GEAR_X..=GEAR_X + GEAR_SIZE).contains(&mx) && (GEAR_Y..=GEAR_Y + GEAR_SIZE).contains(&my);
 
     draw_rectangle(
         GEAR_X,
@@ -691,7 +965,7 @@ fn draw_gear_panel(open: &mut bool, animations_enabled: &mut bool, can_forfeit:
 
     let panel_x = GEAR_X;
     let panel_y = GEAR_Y + GEAR_SIZE + 4.0;
-    let panel_h = PANEL_ITEM_H * 3.0 + 8.0;
+    let panel_h = PANEL_ITEM_H * PANEL_ITEMS + 8.0;
 
     draw_rectangle(panel_x, panel_y, PANEL_W, panel_h, Color::from_rgba(32, 32, 42, 240));
     draw_rectangle_lines(panel_x, panel_y, PANEL_W, panel_h, 2.0, WHITE);
@@ -712,8 +986,141 @@ fn draw_gear_panel(open: &mut bool, animations_enabled: &mut bool, can_forfeit:
         *animations_enabled = !*animations_enabled;
     }
 
+    // --- Eval bar toggle ---
+    let eval_y = row0_y + PANEL_ITEM_H;
+    let eval_hovered = can_show_eval
+        && mx >= panel_x
+        && mx <= panel_x + PANEL_W
+        && my >= eval_y
+        && my <= eval_y + PANEL_ITEM_H;
+    draw_rectangle(
+        panel_x + 2.0,
+        eval_y,
+        PANEL_W - 4.0,
+        PANEL_ITEM_H,
+        if eval_hovered {
+            Color::from_rgba(60, 60, 72, 255)
+        } else {
+            Color::from_rgba(0, 0, 0, 0)
+        },
+    );
+    let eval_label = if *eval_bar_enabled {
+        "Eval Bar  ON"
+    } else {
+        "Eval Bar  OFF"
+    };
+    let eval_color = if !can_show_eval {
+        DARKGRAY
+    } else if *eval_bar_enabled {
+        GOLD
+    } else {
+        LIGHTGRAY
+    };
+    draw_text(eval_label, panel_x + 12.0, eval_y + 26.0, 20.0, eval_color);
+    if eval_hovered && is_mouse_button_pressed(MouseButton::Left) {
+        *eval_bar_enabled = !*eval_bar_enabled;
+    }
+
+    // --- Take Back ---
+    let back_y = eval_y + PANEL_ITEM_H;
+    let back_hovered = can_take_back
+        && mx >= panel_x
+        && mx <= panel_x + PANEL_W
+        && my >= back_y
+        && my <= back_y + PANEL_ITEM_H;
+    draw_rectangle(
+        panel_x + 2.0,
+        back_y,
+        PANEL_W - 4.0,
+        PANEL_ITEM_H,
+        if back_hovered {
+            Color::from_rgba(60, 60, 72, 255)
+        } else {
+            Color::from_rgba(0, 0, 0, 0)
+        },
+    );
+    draw_text(
+        "Take Back",
+        panel_x + 12.0,
+        back_y + 26.0,
+        20.0,
+        if can_take_back { WHITE } else { DARKGRAY },
+    );
+    if back_hovered && is_mouse_button_pressed(MouseButton::Left) {
+        action = GearAction::TakeBack;
+    }
+
+    // --- Redo ---
+    let redo_y = back_y + PANEL_ITEM_H;
+    let redo_hovered = can_redo
+        && mx >= panel_x
+        && mx <= panel_x + PANEL_W
+        && my >= redo_y
+        && my <= redo_y + PANEL_ITEM_H;
+    draw_rectangle(
+        panel_x + 2.0,
+        redo_y,
+        PANEL_W - 4.0,
+        PANEL_ITEM_H,
+        if redo_hovered {
+            Color::from_rgba(60, 60, 72, 255)
+        } else {
+            Color::from_rgba(0, 0, 0, 0)
+        },
+    );
+    draw_text(
+        "Redo",
+        panel_x + 12.0,
+        redo_y + 26.0,
+        20.0,
+        if can_redo { WHITE } else { DARKGRAY },
+    );
+    if redo_hovered && is_mouse_button_pressed(MouseButton::Left) {
+        action = GearAction::Redo;
+    }
+
+    // --- Copy FEN ---
+    let fen_y = redo_y + PANEL_ITEM_H;
+    let fen_hovered =
+        mx >= panel_x && mx <= panel_x + PANEL_W && my >= fen_y && my <= fen_y + PANEL_ITEM_H;
+    draw_rectangle(
+        panel_x + 2.0,
+        fen_y,
+        PANEL_W - 4.0,
+        PANEL_ITEM_H,
+        if fen_hovered {
+            Color::from_rgba(60, 60, 72, 255)
+        } else {
+            Color::from_rgba(0, 0, 0, 0)
+        },
+    );
+    draw_text("Copy FEN", panel_x + 12.0, fen_y + 26.0, 20.0, WHITE);
+    if fen_hovered && is_mouse_button_pressed(MouseButton::Left) {
+        action = GearAction::CopyFen;
+    }
+
+    // --- Copy PGN ---
+    let pgn_y = fen_y + PANEL_ITEM_H;
+    let pgn_hovered =
+        mx >= panel_x && mx <= panel_x + PANEL_W && my >= pgn_y && my <= pgn_y + PANEL_ITEM_H;
+    draw_rectangle(
+        panel_x + 2.0,
+        pgn_y,
+        PANEL_W - 4.0,
+        PANEL_ITEM_H,
+        if pgn_hovered {
+            Color::from_rgba(60, 60, 72, 255)
+        } else {
+            Color::from_rgba(0, 0, 0, 0)
+        },
+    );
+    draw_text("Copy PGN", panel_x + 12.0, pgn_y + 26.0, 20.0, WHITE);
+    if pgn_hovered && is_mouse_button_pressed(MouseButton::Left) {
+        action = GearAction::CopyPgn;
+    }
+
     // --- Forfeit ---
-    let row1_y = row0_y + PANEL_ITEM_H;
+    let row1_y = pgn_y + PANEL_ITEM_H;
     let row1_hovered = can_forfeit && mx >= panel_x && mx <= panel_x + PANEL_W && my >= row1_y && my <= row1_y + PANEL_ITEM_H;
     draw_rectangle(
         panel_x + 2.0, row1_y, PANEL_W - 4.0, PANEL_ITEM_H,
@@ -745,8 +1152,6 @@ fn draw_pieces(
     textures: &PieceTextures,
     camera: &Camera2D,
     current_tile_size: f32,
-    anim_state: &mut PieceAnimationState,
-    current_time: f32,
     perspective: PieceColor,
     animations_enabled: bool,
     skip_pos: Option<Position>,
@@ -761,27 +1166,16 @@ fn draw_pieces(
                 continue;
             }
             if let Some(piece) = game.board[row][col].piece {
-                let tex = if let Some(frames) =
-                    textures.get_animation(piece.kind, piece.color, AnimationState::Idle)
-                {
-                    if animations_enabled {
-                        anim_state.update(current_time, frames.len());
-                        &frames[anim_state.current_frame]
-                    } else {
-                        &frames[0]
-                    }
-                } else {
-                    match textures.get(piece.kind, piece.color) {
-                        Some(t) => t,
-                        None => continue,
-                    }
+                let Some((tex, tint)) = piece_texture(game, textures, piece, animations_enabled)
+                else {
+                    continue;
                 };
 
                 draw_texture_ex(
                     tex,
                     col as f32 * current_tile_size,
                     row as f32 * current_tile_size,
-                    WHITE,
+                    tint,
                     DrawTextureParams {
                         dest_size: Some(vec2(current_tile_size, current_tile_size)),
                         rotation,
@@ -794,7 +1188,9 @@ fn draw_pieces(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_snap_anim(
+    game: &Game,
     anim: &PieceSnapAnimation,
     textures: &PieceTextures,
     tile_size: f32,
@@ -802,21 +1198,9 @@ fn draw_snap_anim(
     perspective: PieceColor,
     now: f32,
     animations_enabled: bool,
-    anim_state: &mut PieceAnimationState,
 ) {
-    let tex = if let Some(frames) =
-        textures.get_animation(anim.piece.kind, anim.piece.color, AnimationState::Idle)
-    {
-        if animations_enabled {
-            &frames[anim_state.current_frame.min(frames.len().saturating_sub(1))]
-        } else {
-            &frames[0]
-        }
-    } else {
-        match textures.get(anim.piece.kind, anim.piece.color) {
-            Some(t) => t,
-            None => return,
-        }
+    let Some((tex, tint)) = piece_texture(game, textures, anim.piece, animations_enabled) else {
+        return;
     };
 
     let pos = anim.current_pos(now, tile_size);
@@ -829,7 +1213,7 @@ fn draw_snap_anim(
         tex,
         pos.x,
         pos.y,
-        WHITE,
+        tint,
         DrawTextureParams {
             dest_size: Some(vec2(tile_size, tile_size)),
             rotation,
@@ -839,151 +1223,127 @@ fn draw_snap_anim(
     );
 }
 
-fn process_click(
-    _game: &Game,
-    camera: &Camera2D,
-    current_tile_size: f32,
-) -> Option<(Position, Position)> {
-    if is_mouse_button_pressed(MouseButton::Left) {
-        // Convert screen position → world position using the SAME camera
-        let world = camera.screen_to_world(mouse_position().into());
+fn process_click(_game: &Game, camera: &Camera2D, current_tile_size: f32) -> Option<Move> {
+    let pos = clicked_square(camera, current_tile_size)?;
 
-        let mx = world.x;
-        let my = world.y;
-
-        if mx < 0.0 || my < 0.0 {
-            return None;
+    unsafe {
+        if let Some(from) = SELECTED {
+            return Some(Move::new(from, pos));
+        } else {
+            SELECTED = Some(pos);
         }
+    }
 
-        let col = (mx / current_tile_size).floor() as isize;
-        let row = (my / current_tile_size).floor() as isize;
+    None
+}
 
-        if row < 0 || col < 0 || row >= 8 || col >= 8 {
-            return None;
-        }
+/// The board square clicked this frame, if any.
+fn clicked_square(camera: &Camera2D, current_tile_size: f32) -> Option<Position> {
+    if !is_mouse_button_pressed(MouseButton::Left) {
+        return None;
+    }
 
-        let pos = Position {
-            row: row as usize,
-            col: col as usize,
-        };
+    // Convert screen position → world position using the SAME camera
+    let world = camera.screen_to_world(mouse_position().into());
 
-        unsafe {
-            if let Some(from) = SELECTED {
-                return Some((from, pos));
-            } else {
-                SELECTED = Some(pos);
-            }
-        }
+    let mx = world.x;
+    let my = world.y;
+
+    if mx < 0.0 || my < 0.0 {
+        return None;
     }
 
-    None
+    let col = (mx / current_tile_size).floor() as isize;
+    let row = (my / current_tile_size).floor() as isize;
+
+    if row < 0 || col < 0 || row >= 8 || col >= 8 {
+        return None;
+    }
+
+    Some(Position {
+        row: row as usize,
+        col: col as usize,
+    })
 }
 
-fn process_keyboard_input(_game: &Game) -> Option<(Position, Position)> {
+fn process_keyboard_input(game: &Game, move_input: &mut String) -> Option<Move> {
     unsafe {
         // Check for Escape to cancel everything (like vim)
         if is_key_pressed(KeyCode::Escape) {
             SELECTED = None;
             TYPING_MODE = false;
             HIGHLIGHTED_COLUMN = None;
+            move_input.clear();
             return None;
         }
 
-        // Check for Enter key to enable typing mode
-        if is_key_pressed(KeyCode::Enter) {
-            if let Some(_) = SELECTED {
-                TYPING_MODE = true;
-                HIGHLIGHTED_COLUMN = None;
+        // Moves are typed in SAN, e.g. `Nf3`, `exd5`, `O-O` or `G@e4`
+        while let Some(ch) = get_char_pressed() {
+            if (ch.is_ascii_alphanumeric() || "=+#-@".contains(ch)) && move_input.len() < 10 {
+                move_input.push(ch);
             }
         }
-
-        // Check for column letter press (A-H)
-        if let Some(col) = process_chess_column() {
-            HIGHLIGHTED_COLUMN = Some(col);
+        if is_key_pressed(KeyCode::Backspace) {
+            move_input.pop();
         }
 
-        // Check for row number press (1-8)
-        if let Some(row) = process_chess_row() {
-            if let Some(col) = HIGHLIGHTED_COLUMN {
-                let pos = Position { row, col };
-
-                if let Some(from) = SELECTED {
-                    if TYPING_MODE {
-                        // In typing mode, this is the destination
-                        HIGHLIGHTED_COLUMN = None;
-                        TYPING_MODE = false;
-                        return Some((from, pos));
-                    } else {
-                        // Not in typing mode, select this position
-                        SELECTED = Some(pos);
-                        HIGHLIGHTED_COLUMN = None;
-                    }
-                } else {
-                    // No piece selected yet, select this position
-                    SELECTED = Some(pos);
-                    HIGHLIGHTED_COLUMN = None;
-                }
-            }
+        TYPING_MODE = !move_input.is_empty();
+        // Highlight the file of the square being typed
+        HIGHLIGHTED_COLUMN = move_input
+            .chars()
+            .rev()
+            .find(|c| ('a'..='h').contains(c))
+            .map(|c| c as usize - 'a' as usize);
+
+        if is_key_pressed(KeyCode::Enter) && !move_input.is_empty() {
+            let mv = parse_typed_move(game, move_input);
+            move_input.clear();
+            TYPING_MODE = false;
+            HIGHLIGHTED_COLUMN = None;
+            return mv;
         }
 
         None
     }
 }
 
-fn process_chess_column() -> Option<usize> {
-    if is_key_pressed(KeyCode::A) {
-        return Some(0);
+/// Reads a typed move as SAN, allowing a lower case piece letter when that
+/// isn't also a pawn move, then as coordinates such as `e2e4` for moves
+/// queued while the opponent is thinking.
+fn parse_typed_move(game: &Game, text: &str) -> Option<Move> {
+    if let Some(mv) = parse_san(game, text) {
+        return Some(mv);
     }
-    if is_key_pressed(KeyCode::B) {
-        return Some(1);
-    }
-    if is_key_pressed(KeyCode::C) {
-        return Some(2);
-    }
-    if is_key_pressed(KeyCode::D) {
-        return Some(3);
-    }
-    if is_key_pressed(KeyCode::E) {
-        return Some(4);
-    }
-    if is_key_pressed(KeyCode::F) {
-        return Some(5);
-    }
-    if is_key_pressed(KeyCode::G) {
-        return Some(6);
-    }
-    if is_key_pressed(KeyCode::H) {
-        return Some(7);
+
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    if "kqrnb".contains(first) {
+        let capitalised = format!("{}{}", first.to_ascii_uppercase(), chars.as_str());
+        if let Some(mv) = parse_san(game, &capitalised) {
+            return Some(mv);
+        }
     }
-    None
+
+    parse_move(text)
 }
 
-fn process_chess_row() -> Option<usize> {
-    if is_key_pressed(KeyCode::Key1) {
-        return Some(0);
-    } // Row 1 = index 0 (top)
-    if is_key_pressed(KeyCode::Key2) {
-        return Some(1);
-    }
-    if is_key_pressed(KeyCode::Key3) {
-        return Some(2);
-    }
-    if is_key_pressed(KeyCode::Key4) {
-        return Some(3);
-    }
-    if is_key_pressed(KeyCode::Key5) {
-        return Some(4);
-    }
-    if is_key_pressed(KeyCode::Key6) {
-        return Some(5);
-    }
-    if is_key_pressed(KeyCode::Key7) {
-        return Some(6);
+fn draw_move_input(move_input: &str, current_tile_size: f32) {
+    if move_input.is_empty() {
+        return;
     }
-    if is_key_pressed(KeyCode::Key8) {
-        return Some(7);
-    } // Row 8 = index 7 (bottom)
-    None
+
+    let board_size = current_tile_size * 8.0;
+    let board_left = (screen_width() - board_size) / 2.0;
+    let board_top = (screen_height() - board_size) / 2.0;
+    let font_size = current_tile_size * 0.3;
+
+    draw_text(
+        &format!("Move: {}_", move_input),
+        board_left,
+        board_top - font_size * 0.4,
+        font_size,
+        Color::from_rgba(0, 255, 0, 220),
+    );
 }
 
 fn draw_highlighted_column(current_tile_size: f32) {
@@ -1002,7 +1362,7 @@ fn draw_highlighted_column(current_tile_size: f32) {
     }
 }
 
-fn draw_queued_move(current_tile_size: f32, queued_auto_move: Option<AutoMove>) {
+fn draw_queued_move(current_tile_size: f32, queued_auto_move: Option<&Move>) {
     if let Some(auto_move) = queued_auto_move {
         draw_rectangle(
             auto_move.from.col as f32 * current_tile_size,
@@ -1023,11 +1383,8 @@ fn draw_queued_move(current_tile_size: f32, queued_auto_move: Option<AutoMove>)
 
 fn draw_row_numbers(perspective: PieceColor, current_tile_size: f32) {
     unsafe {
-        let show_numbers = if let Some(_) = SELECTED {
-            true
-        } else {
-            TYPING_MODE
-        };
+        let selected = SELECTED;
+        let show_numbers = selected.is_some() || TYPING_MODE;
 
         if show_numbers {
             let font_size = current_tile_size * 0.4;
@@ -1083,6 +1440,19 @@ fn can_interact(
     }
 }
 
+fn is_computer_turn(computer: &Option<ComputerPlayer>, current_turn: PieceColor) -> bool {
+    computer
+        .as_ref()
+        .is_some_and(|bot| bot.color() == current_turn)
+}
+
+fn opponent(color: PieceColor) -> PieceColor {
+    match color {
+        White => Black,
+        Black => White,
+    }
+}
+
 fn can_queue_auto_move(
     session: &Option<OnlineSession>,
     connection_ready: bool,
@@ -1094,7 +1464,11 @@ fn can_queue_auto_move(
     }
 }
 
-fn is_move_legal_for_color(game: &Game, from: Position, to: Position, color: PieceColor) -> bool {
+fn is_move_legal_for_color(game: &Game, mv: &Move, color: PieceColor) -> bool {
+    if mv.places_gate {
+        return game.check_placement(mv.to, color).is_ok();
+    }
+    let (from, to) = (mv.from, mv.to);
     if let Some(piece) = game.board[from.row][from.col].piece {
         if piece.color != color {
             return false;
@@ -1108,40 +1482,70 @@ fn is_move_legal_for_color(game: &Game, from: Position, to: Position, color: Pie
     }
 }
 
+/// Plays `mv` and adds it to the history panel, in SAN worked out from the
+/// position before the move.
+fn play_move(game: &mut Game, move_history: &mut MoveHistory, mv: Move) -> Result<Move, MoveError> {
+    let san = to_san(game, &mv);
+    let played = game.make_move(mv)?;
+    move_history.add_move(san);
+    Ok(played)
+}
+
 fn update_game_over_state(game: &Game, game_over: &mut bool, winner: &mut Option<PieceColor>) {
-    if game.is_checkmate(game.current_turn) {
-        *game_over = true;
-        *winner = Some(if game.current_turn == White {
-            Black
-        } else {
-            White
-        });
-    } else if game.is_stalemate(game.current_turn) {
-        *game_over = true;
-        *winner = None;
+    match game.result {
+        GameResult::InProgress => {}
+        GameResult::Checkmate(color) => {
+            *game_over = true;
+            *winner = Some(color);
+        }
+        _ => {
+            *game_over = true;
+            *winner = None;
+        }
     }
 }
 
+/// PGN result for a game that ended on time or by forfeit, which `Game`
+/// itself doesn't know about.
+fn conceded_result(conceded: bool, winner: Option<PieceColor>) -> Option<String> {
+    match (conceded, winner) {
+        (true, Some(White)) => Some("1-0".to_string()),
+        (true, Some(Black)) => Some("0-1".to_string()),
+        _ => None,
+    }
+}
+
+fn draw_reason(result: &GameResult) -> Option<&'static str> {
+    match result {
+        GameResult::Stalemate => Some("stalemate"),
+        GameResult::FiftyMoveRule => Some("fifty-move rule"),
+        GameResult::ThreefoldRepetition => Some("repetition"),
+        GameResult::InsufficientMaterial => Some("insufficient material"),
+        GameResult::Checkmate(_) | GameResult::InProgress => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn update_auto_move(
     game: &Game,
     session: &Option<OnlineSession>,
-    queued_auto_move: &mut Option<AutoMove>,
+    queued_auto_move: &mut Option<Move>,
     status_message: &mut Option<String>,
     status_visible: &mut bool,
     status_expire_turn: &mut Option<u32>,
     status_started_at: &mut Option<f64>,
     turn_count: u32,
-    from: Position,
-    to: Position,
+    candidate: Move,
 ) {
     let local_color = match session {
         Some(online) => online.local_color(),
         None => return,
     };
 
-    let candidate = AutoMove { from, to };
-
-    if *queued_auto_move == Some(candidate) {
+    if queued_auto_move
+        .as_ref()
+        .is_some_and(|queued| queued.matches(&candidate))
+    {
         *queued_auto_move = None;
         set_status_message(
             status_message,
@@ -1154,7 +1558,7 @@ fn update_auto_move(
         return;
     }
 
-    if is_move_legal_for_color(game, from, to, local_color) {
+    if is_move_legal_for_color(game, &candidate, local_color) {
         *queued_auto_move = Some(candidate);
         set_status_message(
             status_message,
@@ -1176,6 +1580,7 @@ fn update_auto_move(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn try_local_move(
     game: &mut Game,
     move_history: &mut MoveHistory,
@@ -1189,14 +1594,13 @@ fn try_local_move(
     status_expire_turn: &mut Option<u32>,
     status_started_at: &mut Option<f64>,
     turn_count: u32,
-    from: Position,
-    to: Position,
+    mv: Move,
 ) {
     match session.as_ref().map(|online| online.role()) {
         Some(SessionRole::Client) => {
-            if game.get_legal_moves(from).contains(&to) {
+            if is_move_legal_for_color(game, &mv, game.current_turn) {
                 if let Some(online) = session {
-                    online.send(NetworkCommand::SubmitMove(from, to));
+                    online.send(NetworkCommand::SubmitMove(mv));
                     set_status_message(
                         status_message,
                         status_visible,
@@ -1219,14 +1623,13 @@ fn try_local_move(
         }
         Some(SessionRole::Host) => {
             let mover = game.current_turn;
-            if game.make_move(from, to).is_ok() {
-                move_history.add_move(from, to);
+            if let Ok(played) = play_move(game, move_history, mv) {
                 clock.apply_increment(mover, get_time());
                 update_game_over_state(game, game_over, winner);
                 *game_over_banner_visible = true;
 
                 if let Some(online) = session {
-                    online.send(NetworkCommand::BroadcastMove(from, to));
+                    online.send(NetworkCommand::BroadcastMove(played));
                 }
 
                 set_status_message(
@@ -1250,8 +1653,7 @@ fn try_local_move(
         }
         Some(SessionRole::Local) | None => {
             let mover = game.current_turn;
-            if game.make_move(from, to).is_ok() {
-                move_history.add_move(from, to);
+            if play_move(game, move_history, mv).is_ok() {
                 clock.apply_increment(mover, get_time());
                 update_game_over_state(game, game_over, winner);
                 *game_over_banner_visible = true;
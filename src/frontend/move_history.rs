@@ -1,9 +1,7 @@
-use crate::game::Position;
 use macroquad::prelude::*;
 
 struct MoveEntry {
-    from: Position,
-    to: Position,
+    san: String,
     added_at: f64,
 }
 
@@ -22,27 +20,22 @@ impl MoveHistory {
         }
     }
 
-    pub fn add_move(&mut self, from: Position, to: Position) {
+    pub fn add_move(&mut self, san: String) {
         self.moves.push(MoveEntry {
-            from,
-            to,
+            san,
             added_at: get_time(),
         });
     }
 
-    pub fn position_to_algebraic(pos: &Position) -> String {
-        format!("{}{}", (b'a' + pos.col as u8) as char, pos.row + 1)
+    pub fn pop_move(&mut self) {
+        self.moves.pop();
     }
 
-    fn format_move(&self, idx: usize, from: Position, to: Position) -> String {
-        let move_num = (idx / 2) + 1;
-        let from_sq = Self::position_to_algebraic(&from);
-        let to_sq = Self::position_to_algebraic(&to);
-
-        if idx % 2 == 0 {
-            format!("{}. {}{}", move_num, from_sq, to_sq)
+    fn format_move(&self, idx: usize, san: &str) -> String {
+        if idx.is_multiple_of(2) {
+            format!("{}. {}", (idx / 2) + 1, san)
         } else {
-            format!("{}{}", from_sq, to_sq)
+            san.to_string()
         }
     }
 
@@ -100,7 +93,7 @@ impl MoveHistory {
 
         if self.open_progress > 0.6 {
             for (i, entry) in self.moves.iter().enumerate().skip(start_idx) {
-                let move_text = self.format_move(i, entry.from, entry.to);
+                let move_text = self.format_move(i, &entry.san);
                 let visible_chars =
                     typed_char_count(&move_text, current_time - entry.added_at, 28.0);
                 let typed_text: String = move_text.chars().take(visible_chars).collect();
@@ -114,11 +107,8 @@ impl MoveHistory {
             {
                 self.collapsed = false;
             } else if self.open_progress > 0.15
-                && is_hovered(collapse_x, button_y, button_size, button_size)
-            {
-                self.collapsed = true;
-            } else if self.open_progress > 0.15
-                && is_hovered(close_x, button_y, button_size, button_size)
+                && (is_hovered(collapse_x, button_y, button_size, button_size)
+                    || is_hovered(close_x, button_y, button_size, button_size))
             {
                 self.collapsed = true;
             }
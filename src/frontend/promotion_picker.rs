@@ -0,0 +1,95 @@
+use crate::frontend::load_pieces::PieceTextures;
+use crate::pieces::Color as PieceColor;
+use crate::pieces::PieceType;
+use macroquad::prelude::*;
+
+pub struct PromotionPicker;
+
+impl PromotionPicker {
+    pub fn draw(color: PieceColor, textures: &PieceTextures) -> Option<PieceType> {
+        let option_size = 96.0;
+        let spacing = 16.0;
+        let panel_width = option_size * 4.0 + spacing * 5.0;
+        let panel_height = option_size + 96.0;
+        let panel_x = (screen_width() - panel_width) / 2.0;
+        let panel_y = (screen_height() - panel_height) / 2.0;
+
+        draw_rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::from_rgba(0, 0, 0, 150),
+        );
+
+        draw_rectangle(
+            panel_x,
+            panel_y,
+            panel_width,
+            panel_height,
+            Color::from_rgba(40, 40, 40, 255),
+        );
+        draw_rectangle_lines(panel_x, panel_y, panel_width, panel_height, 4.0, GOLD);
+
+        draw_text("Promote to", panel_x + spacing, panel_y + 40.0, 36.0, WHITE);
+        draw_text(
+            "Esc to cancel",
+            panel_x + panel_width - 140.0,
+            panel_y + 38.0,
+            20.0,
+            LIGHTGRAY,
+        );
+
+        let mut chosen = None;
+
+        for (i, kind) in PieceType::PROMOTIONS.iter().enumerate() {
+            let x = panel_x + spacing + i as f32 * (option_size + spacing);
+            let y = panel_y + 64.0;
+            let hovered = is_hovered(x, y, option_size, option_size);
+
+            draw_rectangle(
+                x,
+                y,
+                option_size,
+                option_size,
+                if hovered {
+                    Color::from_rgba(80, 80, 90, 255)
+                } else {
+                    Color::from_rgba(60, 60, 70, 255)
+                },
+            );
+            draw_rectangle_lines(
+                x,
+                y,
+                option_size,
+                option_size,
+                2.0,
+                if hovered { GOLD } else { WHITE },
+            );
+
+            if let Some(tex) = textures.get(*kind, color) {
+                draw_texture_ex(
+                    tex,
+                    x,
+                    y,
+                    WHITE,
+                    DrawTextureParams {
+                        dest_size: Some(vec2(option_size, option_size)),
+                        ..Default::default()
+                    },
+                );
+            }
+
+            if hovered && is_mouse_button_pressed(MouseButton::Left) {
+                chosen = Some(*kind);
+            }
+        }
+
+        chosen
+    }
+}
+
+fn is_hovered(x: f32, y: f32, width: f32, height: f32) -> bool {
+    let (mx, my) = mouse_position();
+    mx >= x && mx <= x + width && my >= y && my <= y + height
+}
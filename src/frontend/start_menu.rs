@@ -1,35 +1,115 @@
 use macroquad::prelude::*;
-
+use macroquad::rand::{gen_range, srand};
+
+use crate::ai::Strength;
+use crate::board::CHESS960_POSITIONS;
+use crate::game::Game;
+use crate::game::fen::START_FEN;
+use crate::game::setups::GATED_STARTS;
+use crate::gates::GateRules;
 use crate::network::SessionConfig;
+use crate::parser::pgn::import_pgn;
+use crate::pieces::PieceType;
 use crate::time_control::{STANDARD_TIME_CONTROLS, TimeControl};
 
 pub struct LaunchConfig {
     pub session: SessionConfig,
     pub time_control: TimeControl,
+    /// Online games that join someone else's take the host's rules instead.
+    pub gate_rules: GateRules,
+    /// FEN the game starts from, which joining games also take from the host.
+    pub start_fen: String,
+}
+
+/// Position local games start from, given on the command line as
+/// `--fen <position>` or `--pgn <file>`.
+pub enum StartPosition {
+    Fen(String),
+    Pgn(String),
+}
+
+impl StartPosition {
+    pub fn from_args(args: impl Iterator<Item = String>) -> Option<StartPosition> {
+        let args: Vec<String> = args.collect();
+        let value_after = |flag: &str| args.iter().skip_while(|arg| *arg != flag).nth(1).cloned();
+
+        if let Some(path) = value_after("--pgn") {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => return Some(StartPosition::Pgn(text)),
+                Err(err) => eprintln!("Could not read {}: {}", path, err),
+            }
+        }
+        value_after("--fen").map(StartPosition::Fen)
+    }
+
+    pub fn load(&self) -> Result<Game, String> {
+        match self {
+            StartPosition::Fen(fen) => Game::from_fen(fen).map_err(|err| format!("{:?}", err)),
+            StartPosition::Pgn(text) => import_pgn(text)
+                .map(|(_, game)| game)
+                .map_err(|err| format!("{:?}", err)),
+        }
+    }
 }
 
 enum StartStep {
     ModeSelect,
+    ComputerStrength,
     TimeSelect(SessionConfig),
+    GateRulesSelect(SessionConfig, TimeControl),
+    PositionSelect(SessionConfig, TimeControl),
+}
+
+/// Which kind of starting position the position screen has picked.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StartChoice {
+    Standard,
+    Chess960,
+    /// One of `GATED_STARTS`.
+    Gated(usize),
+    Fen,
 }
 
 pub struct StartMenu {
     address_input: String,
     step: StartStep,
+    /// Kept between visits to the gate rules screen.
+    gate_rules: GateRules,
+    /// Kept between visits to the position screen.
+    start_choice: StartChoice,
+    chess960_number: u16,
+    fen_input: String,
 }
 
 impl StartMenu {
     pub fn new() -> Self {
+        // Chess960 shuffles should differ from one run to the next
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        srand(seed);
+
         Self {
             address_input: "127.0.0.1:4000".to_string(),
             step: StartStep::ModeSelect,
+            gate_rules: GateRules::STANDARD,
+            start_choice: StartChoice::Standard,
+            chess960_number: 518,
+            fen_input: String::new(),
         }
     }
 
     pub fn draw(&mut self) -> Option<LaunchConfig> {
         match &self.step {
             StartStep::ModeSelect => self.draw_mode_select(),
+            StartStep::ComputerStrength => self.draw_strength_select(),
             StartStep::TimeSelect(session) => self.draw_time_select(session.clone()),
+            StartStep::GateRulesSelect(session, time_control) => {
+                self.draw_gate_rules_select(session.clone(), *time_control)
+            }
+            StartStep::PositionSelect(session, time_control) => {
+                self.draw_position_select(session.clone(), *time_control)
+            }
         }
     }
 
@@ -37,7 +117,7 @@ impl StartMenu {
         self.handle_text_input();
 
         let menu_width = 400.0;
-        let menu_height = 640.0;
+        let menu_height = 720.0;
         let menu_x = (screen_width() - menu_width) / 2.0;
         let menu_y = (screen_height() - menu_height) / 2.0;
 
@@ -119,7 +199,20 @@ impl StartMenu {
             find_hovered,
         );
 
-        let input_y = menu_y + 510.0;
+        // Computer button
+        let computer_button_y = menu_y + 500.0;
+        let computer_hovered =
+            Self::is_button_hovered(button_x, computer_button_y, button_width, button_height);
+        Self::draw_button(
+            "Play Computer",
+            button_x,
+            computer_button_y,
+            button_width,
+            button_height,
+            computer_hovered,
+        );
+
+        let input_y = menu_y + 590.0;
         draw_text("Address", button_x, input_y - 12.0, 24.0, LIGHTGRAY);
         draw_rectangle(
             button_x,
@@ -158,11 +251,15 @@ impl StartMenu {
                         server_addr: self.address_input.trim().to_string(),
                     },
                     time_control: STANDARD_TIME_CONTROLS[4],
+                    gate_rules: GateRules::STANDARD,
+                    start_fen: START_FEN.to_string(),
                 });
             } else if find_hovered {
                 self.step = StartStep::TimeSelect(SessionConfig::FindMatch {
                     addr: self.address_input.trim().to_string(),
                 });
+            } else if computer_hovered {
+                self.step = StartStep::ComputerStrength;
             }
         }
 
@@ -173,6 +270,55 @@ impl StartMenu {
         None
     }
 
+    fn draw_strength_select(&mut self) -> Option<LaunchConfig> {
+        let menu_width = 400.0;
+        let menu_height = 440.0;
+        let menu_x = (screen_width() - menu_width) / 2.0;
+        let menu_y = (screen_height() - menu_height) / 2.0;
+
+        draw_rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::from_rgba(20, 20, 30, 255),
+        );
+        draw_rectangle(
+            menu_x,
+            menu_y,
+            menu_width,
+            menu_height,
+            Color::from_rgba(40, 40, 50, 255),
+        );
+        draw_rectangle_lines(menu_x, menu_y, menu_width, menu_height, 4.0, GOLD);
+
+        draw_text("Computer Strength", menu_x + 50.0, menu_y + 70.0, 40.0, WHITE);
+        draw_text("You play White", menu_x + 130.0, menu_y + 104.0, 24.0, LIGHTGRAY);
+
+        let button_width = 300.0;
+        let button_height = 60.0;
+        let button_x = menu_x + (menu_width - button_width) / 2.0;
+
+        for (index, strength) in Strength::ALL.iter().enumerate() {
+            let y = menu_y + 140.0 + index as f32 * 80.0;
+            let hovered = Self::is_button_hovered(button_x, y, button_width, button_height);
+            Self::draw_button(strength.label(), button_x, y, button_width, button_height, hovered);
+
+            if hovered && is_mouse_button_pressed(MouseButton::Left) {
+                self.step = StartStep::TimeSelect(SessionConfig::VsComputer {
+                    strength: *strength,
+                });
+                return None;
+            }
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.step = StartStep::ModeSelect;
+        }
+
+        None
+    }
+
     fn draw_time_select(&mut self, session: SessionConfig) -> Option<LaunchConfig> {
         let menu_width = 620.0;
         let menu_height = 560.0;
@@ -234,10 +380,8 @@ impl StartMenu {
             );
 
             if hovered && is_mouse_button_pressed(MouseButton::Left) {
-                return Some(LaunchConfig {
-                    session: session.clone(),
-                    time_control: *time_control,
-                });
+                self.step = StartStep::GateRulesSelect(session.clone(), *time_control);
+                return None;
             }
         }
 
@@ -266,6 +410,315 @@ impl StartMenu {
         None
     }
 
+    fn draw_gate_rules_select(
+        &mut self,
+        session: SessionConfig,
+        time_control: TimeControl,
+    ) -> Option<LaunchConfig> {
+        let menu_width = 620.0;
+        let menu_height = 680.0;
+        let menu_x = (screen_width() - menu_width) / 2.0;
+        let menu_y = (screen_height() - menu_height) / 2.0;
+
+        draw_rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::from_rgba(20, 20, 30, 255),
+        );
+        draw_rectangle(
+            menu_x,
+            menu_y,
+            menu_width,
+            menu_height,
+            Color::from_rgba(40, 40, 50, 255),
+        );
+        draw_rectangle_lines(menu_x, menu_y, menu_width, menu_height, 4.0, GOLD);
+
+        draw_text("Gate Rules", menu_x + 200.0, menu_y + 70.0, 42.0, WHITE);
+
+        let clicked = is_mouse_button_pressed(MouseButton::Left);
+        let rules = &mut self.gate_rules;
+        let left = menu_x + 64.0;
+
+        // Pieces that leave gates
+        draw_text("Pieces that leave gates", left, menu_y + 130.0, 24.0, LIGHTGRAY);
+        for (index, kind) in PieceType::ALL.iter().enumerate() {
+            let x = left + index as f32 * 84.0;
+            let y = menu_y + 145.0;
+            let hovered = Self::is_button_hovered(x, y, 72.0, 52.0);
+            let label = kind.to_char().to_ascii_uppercase().to_string();
+            Self::draw_toggle(&label, x, y, 72.0, 52.0, hovered, rules.spawns(*kind));
+            if hovered && clicked {
+                rules.spawners[*kind as usize] ^= true;
+            }
+        }
+
+        // How long gates stand
+        let duration_y = menu_y + 230.0;
+        draw_text("Turns a gate stands", left, duration_y + 34.0, 24.0, LIGHTGRAY);
+        let minus_x = left + 300.0;
+        let plus_x = minus_x + 160.0;
+        let minus_hovered = Self::is_button_hovered(minus_x, duration_y, 52.0, 52.0);
+        let plus_hovered = Self::is_button_hovered(plus_x, duration_y, 52.0, 52.0);
+        Self::draw_button("-", minus_x, duration_y, 52.0, 52.0, minus_hovered);
+        Self::draw_button("+", plus_x, duration_y, 52.0, 52.0, plus_hovered);
+        draw_text(
+            &rules.duration.to_string(),
+            minus_x + 98.0,
+            duration_y + 38.0,
+            36.0,
+            WHITE,
+        );
+        if clicked && minus_hovered {
+            rules.duration = rules.duration.saturating_sub(1).max(1);
+        } else if clicked && plus_hovered {
+            rules.duration = (rules.duration + 1).min(GateRules::MAX_DURATION);
+        }
+
+        // Where gates go
+        draw_text("Gates land on", left, menu_y + 320.0, 24.0, LIGHTGRAY);
+        let placements = [
+            ("Path", &mut rules.on_path),
+            ("Origin", &mut rules.on_origin),
+            ("Destination", &mut rules.on_destination),
+        ];
+        for (index, (label, on)) in placements.into_iter().enumerate() {
+            let x = left + index as f32 * 168.0;
+            let y = menu_y + 335.0;
+            let hovered = Self::is_button_hovered(x, y, 156.0, 52.0);
+            Self::draw_toggle(label, x, y, 156.0, 52.0, hovered, *on);
+            if hovered && clicked {
+                *on ^= true;
+            }
+        }
+
+        let owner_y = menu_y + 412.0;
+        let owner_hovered = Self::is_button_hovered(left, owner_y, 492.0, 52.0);
+        Self::draw_toggle(
+            "Gates Block Their Owner",
+            left,
+            owner_y,
+            492.0,
+            52.0,
+            owner_hovered,
+            rules.blocks_owner,
+        );
+        if owner_hovered && clicked {
+            rules.blocks_owner ^= true;
+        }
+
+        // Gates each side may place instead of moving
+        let stock_y = menu_y + 488.0;
+        draw_text("Gates to place", left, stock_y + 34.0, 24.0, LIGHTGRAY);
+        let minus_hovered = Self::is_button_hovered(minus_x, stock_y, 52.0, 52.0);
+        let plus_hovered = Self::is_button_hovered(plus_x, stock_y, 52.0, 52.0);
+        Self::draw_button("-", minus_x, stock_y, 52.0, 52.0, minus_hovered);
+        Self::draw_button("+", plus_x, stock_y, 52.0, 52.0, plus_hovered);
+        draw_text(
+            &rules.placement_stock.to_string(),
+            minus_x + 98.0,
+            stock_y + 38.0,
+            36.0,
+            WHITE,
+        );
+        if clicked && minus_hovered {
+            rules.placement_stock = rules.placement_stock.saturating_sub(1);
+        } else if clicked && plus_hovered {
+            rules.placement_stock = (rules.placement_stock + 1).min(GateRules::MAX_STOCK);
+        }
+
+        let button_y = menu_y + menu_height - 80.0;
+        let button_width = 148.0;
+        let back_hovered = Self::is_button_hovered(left, button_y, button_width, 52.0);
+        let standard_x = left + 172.0;
+        let standard_hovered = Self::is_button_hovered(standard_x, button_y, button_width, 52.0);
+        let next_x = left + 344.0;
+        let next_hovered = Self::is_button_hovered(next_x, button_y, button_width, 52.0);
+        Self::draw_button("Back", left, button_y, button_width, 52.0, back_hovered);
+        Self::draw_button("Standard", standard_x, button_y, button_width, 52.0, standard_hovered);
+        Self::draw_button("Next", next_x, button_y, button_width, 52.0, next_hovered);
+
+        if clicked && standard_hovered {
+            *rules = GateRules::STANDARD;
+        } else if clicked && next_hovered {
+            self.step = StartStep::PositionSelect(session, time_control);
+        } else if (clicked && back_hovered) || is_key_pressed(KeyCode::Escape) {
+            self.step = StartStep::TimeSelect(session);
+        }
+
+        None
+    }
+
+    fn draw_position_select(
+        &mut self,
+        session: SessionConfig,
+        time_control: TimeControl,
+    ) -> Option<LaunchConfig> {
+        let menu_width = 620.0;
+        let menu_height = 680.0;
+        let menu_x = (screen_width() - menu_width) / 2.0;
+        let menu_y = (screen_height() - menu_height) / 2.0;
+
+        draw_rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::from_rgba(20, 20, 30, 255),
+        );
+        draw_rectangle(
+            menu_x,
+            menu_y,
+            menu_width,
+            menu_height,
+            Color::from_rgba(40, 40, 50, 255),
+        );
+        draw_rectangle_lines(menu_x, menu_y, menu_width, menu_height, 4.0, GOLD);
+
+        draw_text("Starting Position", menu_x + 140.0, menu_y + 70.0, 42.0, WHITE);
+
+        let clicked = is_mouse_button_pressed(MouseButton::Left);
+        let left = menu_x + 64.0;
+        let width = 492.0;
+
+        let standard_y = menu_y + 100.0;
+        let standard_hovered = Self::is_button_hovered(left, standard_y, width, 52.0);
+        Self::draw_toggle(
+            "Standard",
+            left,
+            standard_y,
+            width,
+            52.0,
+            standard_hovered,
+            self.start_choice == StartChoice::Standard,
+        );
+
+        // Chess960, with a button to deal a new layout
+        let chess960_y = menu_y + 166.0;
+        let chess960_width = 332.0;
+        let shuffle_x = left + chess960_width + 12.0;
+        let shuffle_width = width - chess960_width - 12.0;
+        let chess960_hovered = Self::is_button_hovered(left, chess960_y, chess960_width, 52.0);
+        let shuffle_hovered = Self::is_button_hovered(shuffle_x, chess960_y, shuffle_width, 52.0);
+        Self::draw_toggle(
+            &format!("Chess960 #{}", self.chess960_number),
+            left,
+            chess960_y,
+            chess960_width,
+            52.0,
+            chess960_hovered,
+            self.start_choice == StartChoice::Chess960,
+        );
+        Self::draw_button("Shuffle", shuffle_x, chess960_y, shuffle_width, 52.0, shuffle_hovered);
+
+        draw_text("Gated starts", left, menu_y + 256.0, 24.0, LIGHTGRAY);
+        let mut gated_clicked = None;
+        for (index, start) in GATED_STARTS.iter().enumerate() {
+            let y = menu_y + 270.0 + index as f32 * 62.0;
+            let hovered = Self::is_button_hovered(left, y, width, 52.0);
+            let on = self.start_choice == StartChoice::Gated(index);
+            Self::draw_toggle(start.name, left, y, width, 52.0, hovered, on);
+            if hovered && clicked {
+                gated_clicked = Some(index);
+            }
+        }
+
+        // Any position, typed in as FEN
+        let fen_y = menu_y + 470.0;
+        let fen_hovered = Self::is_button_hovered(left, fen_y, width, 52.0);
+        let fen_on = self.start_choice == StartChoice::Fen;
+        if fen_on {
+            self.handle_fen_input();
+        }
+        draw_rectangle(left, fen_y, width, 52.0, Color::from_rgba(26, 26, 34, 255));
+        draw_rectangle_lines(left, fen_y, width, 52.0, 2.0, if fen_on { GOLD } else { WHITE });
+        let fen_text = if self.fen_input.is_empty() && !fen_on {
+            "Click to type a FEN"
+        } else {
+            &self.fen_input
+        };
+        // Long FENs show their end, where the typing happens
+        let hidden = fen_text.chars().count().saturating_sub(44);
+        let shown: String = fen_text.chars().skip(hidden).collect();
+        draw_text(&shown, left + 10.0, fen_y + 33.0, 20.0, if fen_on { WHITE } else { GRAY });
+
+        let chosen = self.chosen_fen();
+        if let Err(err) = &chosen {
+            draw_text(err, left, fen_y + 80.0, 20.0, Color::from_rgba(255, 120, 120, 255));
+        }
+
+        let button_y = menu_y + menu_height - 80.0;
+        let button_width = 148.0;
+        let back_hovered = Self::is_button_hovered(left, button_y, button_width, 52.0);
+        let start_x = left + 344.0;
+        let start_hovered = Self::is_button_hovered(start_x, button_y, button_width, 52.0);
+        Self::draw_button("Back", left, button_y, button_width, 52.0, back_hovered);
+        Self::draw_button("Start", start_x, button_y, button_width, 52.0, start_hovered);
+
+        if clicked && standard_hovered {
+            self.start_choice = StartChoice::Standard;
+        } else if clicked && chess960_hovered {
+            self.start_choice = StartChoice::Chess960;
+        } else if clicked && shuffle_hovered {
+            self.chess960_number = gen_range(0, CHESS960_POSITIONS);
+            self.start_choice = StartChoice::Chess960;
+        } else if let Some(index) = gated_clicked {
+            self.start_choice = StartChoice::Gated(index);
+        } else if clicked && fen_hovered {
+            self.start_choice = StartChoice::Fen;
+        } else if clicked && start_hovered {
+            if let Ok(start_fen) = chosen {
+                return Some(LaunchConfig {
+                    session,
+                    time_control,
+                    gate_rules: self.gate_rules,
+                    start_fen,
+                });
+            }
+        } else if (clicked && back_hovered) || is_key_pressed(KeyCode::Escape) {
+            self.step = StartStep::GateRulesSelect(session, time_control);
+        }
+
+        None
+    }
+
+    /// FEN of the position picked on the position screen, or why the typed
+    /// one can't be used.
+    fn chosen_fen(&self) -> Result<String, String> {
+        match self.start_choice {
+            StartChoice::Standard => Ok(START_FEN.to_string()),
+            StartChoice::Chess960 => Ok(Game::chess960(self.chess960_number, self.gate_rules).to_fen()),
+            StartChoice::Gated(index) => Ok(GATED_STARTS[index].fen.to_string()),
+            StartChoice::Fen => match Game::from_fen(self.fen_input.trim()) {
+                Ok(_) => Ok(self.fen_input.trim().to_string()),
+                Err(err) => Err(format!("Not a usable FEN ({:?})", err)),
+            },
+        }
+    }
+
+    fn handle_fen_input(&mut self) {
+        while let Some(ch) = get_char_pressed() {
+            if !ch.is_control() && self.fen_input.len() < 160 {
+                self.fen_input.push(ch);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.fen_input.pop();
+        }
+    }
+
+    /// A button that stays lit while `on`.
+    fn draw_toggle(text: &str, x: f32, y: f32, width: f32, height: f32, hovered: bool, on: bool) {
+        Self::draw_button(text, x, y, width, height, hovered);
+        if on {
+            draw_rectangle(x, y, width, height, Color::from_rgba(255, 203, 0, 60));
+            draw_rectangle_lines(x, y, width, height, 3.0, GOLD);
+        }
+    }
+
     fn draw_button(text: &str, x: f32, y: f32, width: f32, height: f32, hovered: bool) {
         let color = if hovered {
             Color::from_rgba(80, 80, 90, 255)
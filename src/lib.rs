@@ -0,0 +1,15 @@
+// =======================================================
+// Project: GatedChess
+// File: lib.rs
+// Description: Evaluation and search on top of the rules crate, shared by
+//              the game window and the headless engine binary.
+// Author: Seamus Daniello
+// Created: 2026-10-18
+// Last Modified: 2026-10-18
+// License: MIT
+// =======================================================
+
+pub mod ai;
+pub mod eval;
+
+pub use gated_rules::{board, config, game, gates, parser, pieces, time_control};
@@ -7,16 +7,13 @@
 // License: MIT
 // =======================================================
 
-mod board;
-mod config;
 mod frontend; // <-- your GUI code lives in src/frontend/mod.rs
-mod game;
-mod gates;
 mod network;
-mod pieces;
-mod time_control;
+
+use gated_chess::{ai, board, config, eval, game, gates, parser, pieces, time_control};
 
 #[macroquad::main("GatedChess")]
 async fn main() {
-    frontend::run_ui().await;
+    let start_position = frontend::StartPosition::from_args(std::env::args());
+    frontend::run_ui(start_position).await;
 }
@@ -4,8 +4,11 @@ use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use crate::game::Position;
-use crate::pieces::Color;
+use crate::ai::Strength;
+use crate::config::BOARD_SIZE;
+use crate::game::{Game, Move, Position};
+use crate::gates::GateRules;
+use crate::pieces::{Color, PieceType};
 use crate::time_control::TimeControl;
 
 #[derive(Clone, Debug)]
@@ -14,6 +17,8 @@ pub enum SessionConfig {
     Host { bind_addr: String },
     Join { server_addr: String },
     FindMatch { addr: String },
+    /// Single player against the built-in engine, which takes Black.
+    VsComputer { strength: Strength },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -28,7 +33,11 @@ pub enum NetworkEvent {
     WaitingForOpponent(String),
     Connected(String),
     TimeControlUpdated(TimeControl),
-    RemoteMove(Position, Position),
+    /// The host's gate rules, which the game must play by.
+    GateRulesUpdated(GateRules),
+    /// FEN of the position the host's game starts from.
+    StartPositionUpdated(String),
+    RemoteMove(Move),
     InvalidMove(String),
     Disconnected(String),
     Error(String),
@@ -36,8 +45,8 @@ pub enum NetworkEvent {
 
 #[derive(Clone, Debug)]
 pub enum NetworkCommand {
-    SubmitMove(Position, Position),
-    BroadcastMove(Position, Position),
+    SubmitMove(Move),
+    BroadcastMove(Move),
     RejectMove(String),
     Shutdown,
 }
@@ -55,7 +64,12 @@ struct SessionState {
 }
 
 impl OnlineSession {
-    pub fn host(bind_addr: String, time_control: TimeControl) -> Self {
+    pub fn host(
+        bind_addr: String,
+        time_control: TimeControl,
+        gate_rules: GateRules,
+        start_fen: String,
+    ) -> Self {
         let (cmd_tx, cmd_rx) = mpsc::channel();
         let (event_tx, event_rx) = mpsc::channel();
         let state = Arc::new(Mutex::new(SessionState {
@@ -65,7 +79,9 @@ impl OnlineSession {
 
         thread::spawn({
             let state = Arc::clone(&state);
-            move || run_host(bind_addr, time_control, cmd_rx, event_tx, state)
+            move || {
+                run_host(bind_addr, time_control, gate_rules, start_fen, cmd_rx, event_tx, state)
+            }
         });
 
         Self {
@@ -95,7 +111,12 @@ impl OnlineSession {
         }
     }
 
-    pub fn find_match(addr: String, time_control: TimeControl) -> Self {
+    pub fn find_match(
+        addr: String,
+        time_control: TimeControl,
+        gate_rules: GateRules,
+        start_fen: String,
+    ) -> Self {
         let (cmd_tx, cmd_rx) = mpsc::channel();
         let (event_tx, event_rx) = mpsc::channel();
         let state = Arc::new(Mutex::new(SessionState {
@@ -105,7 +126,9 @@ impl OnlineSession {
 
         thread::spawn({
             let state = Arc::clone(&state);
-            move || run_find_match(addr, time_control, cmd_rx, event_tx, state)
+            move || {
+                run_find_match(addr, time_control, gate_rules, start_fen, cmd_rx, event_tx, state)
+            }
         });
 
         Self {
@@ -147,6 +170,8 @@ impl Drop for OnlineSession {
 fn run_host(
     bind_addr: String,
     time_control: TimeControl,
+    gate_rules: GateRules,
+    start_fen: String,
     cmd_rx: Receiver<NetworkCommand>,
     event_tx: Sender<NetworkEvent>,
     state: Arc<Mutex<SessionState>>,
@@ -175,9 +200,12 @@ fn run_host(
 
     let _ = stream.set_nodelay(true);
 
-    if let Err(err) = stream.write_all(format_time_control_line(time_control).as_bytes()) {
+    let handshake = format_time_control_line(time_control)
+        + &format_gate_rules_line(gate_rules)
+        + &format_start_position_line(&start_fen);
+    if let Err(err) = stream.write_all(handshake.as_bytes()) {
         let _ = event_tx.send(NetworkEvent::Error(format!(
-            "Failed to send time control: {}",
+            "Failed to send game settings: {}",
             err
         )));
         return;
@@ -243,6 +271,8 @@ fn run_client(
 fn run_find_match(
     addr: String,
     time_control: TimeControl,
+    gate_rules: GateRules,
+    start_fen: String,
     cmd_rx: Receiver<NetworkCommand>,
     event_tx: Sender<NetworkEvent>,
     state: Arc<Mutex<SessionState>>,
@@ -335,9 +365,20 @@ fn run_find_match(
     };
     let color = assigned_color.unwrap_or(Color::White);
     set_state(&state, role, color);
-    let _ = event_tx.send(NetworkEvent::Connected("Match found!".to_string()));
 
+    // The relay only settles the clock, so White hands its gate rules and
+    // starting position on
     let is_host = role == SessionRole::Host;
+    if is_host {
+        let mut ws = &stream;
+        let settings = format_gate_rules_line(gate_rules) + &format_start_position_line(&start_fen);
+        if ws.write_all(settings.as_bytes()).is_err() {
+            let _ = event_tx.send(NetworkEvent::Error("Failed to send game settings".to_string()));
+            return;
+        }
+    }
+    let _ = event_tx.send(NetworkEvent::Connected("Match found!".to_string()));
+
     let reader_stream = reader.into_inner();
     let reader_tx = event_tx.clone();
 
@@ -367,8 +408,9 @@ fn host_reader_loop(stream: TcpStream, event_tx: Sender<NetworkEvent>) {
                 break;
             }
             Ok(_) => {
-                if let Some((from, to)) = parse_move_line(&line, "TRY") {
-                    let _ = event_tx.send(NetworkEvent::RemoteMove(from, to));
+                let mv = parse_move_line(&line, "TRY").or_else(|| parse_placement_line(&line, "PLACE"));
+                if let Some(mv) = mv {
+                    let _ = event_tx.send(NetworkEvent::RemoteMove(mv));
                 } else {
                     let _ = event_tx.send(NetworkEvent::Error(format!(
                         "Malformed client message: {}",
@@ -404,8 +446,14 @@ fn client_reader_loop(stream: TcpStream, event_tx: Sender<NetworkEvent>) {
 
                 if let Some(time_control) = parse_time_control_line(trimmed) {
                     let _ = event_tx.send(NetworkEvent::TimeControlUpdated(time_control));
-                } else if let Some((from, to)) = parse_move_line(trimmed, "APPLY") {
-                    let _ = event_tx.send(NetworkEvent::RemoteMove(from, to));
+                } else if let Some(gate_rules) = parse_gate_rules_line(trimmed) {
+                    let _ = event_tx.send(NetworkEvent::GateRulesUpdated(gate_rules));
+                } else if let Some(fen) = parse_start_position_line(trimmed) {
+                    let _ = event_tx.send(NetworkEvent::StartPositionUpdated(fen));
+                } else if let Some(mv) = parse_move_line(trimmed, "APPLY")
+                    .or_else(|| parse_placement_line(trimmed, "PLACED"))
+                {
+                    let _ = event_tx.send(NetworkEvent::RemoteMove(mv));
                 } else if let Some(reason) = trimmed.strip_prefix("INVALID ") {
                     let _ = event_tx.send(NetworkEvent::InvalidMove(reason.to_string()));
                 } else {
@@ -434,8 +482,12 @@ fn writer_loop(
 ) {
     while let Ok(command) = cmd_rx.recv() {
         let line = match command {
-            NetworkCommand::SubmitMove(from, to) => format_move_line("TRY", from, to),
-            NetworkCommand::BroadcastMove(from, to) => format_move_line("APPLY", from, to),
+            NetworkCommand::SubmitMove(mv) if mv.places_gate => format_placement_line("PLACE", &mv),
+            NetworkCommand::SubmitMove(mv) => format_move_line("TRY", &mv),
+            NetworkCommand::BroadcastMove(mv) if mv.places_gate => {
+                format_placement_line("PLACED", &mv)
+            }
+            NetworkCommand::BroadcastMove(mv) => format_move_line("APPLY", &mv),
             NetworkCommand::RejectMove(reason) => format!("INVALID {}\n", reason),
             NetworkCommand::Shutdown => break,
         };
@@ -451,11 +503,29 @@ fn writer_loop(
     }
 }
 
-fn format_move_line(prefix: &str, from: Position, to: Position) -> String {
-    format!(
-        "{} {} {} {} {}\n",
-        prefix, from.row, from.col, to.row, to.col
-    )
+fn format_move_line(prefix: &str, mv: &Move) -> String {
+    let (from, to) = (mv.from, mv.to);
+    match mv.promotion {
+        Some(kind) => format!(
+            "{} {} {} {} {} {}\n",
+            prefix,
+            from.row,
+            from.col,
+            to.row,
+            to.col,
+            kind.to_char()
+        ),
+        None => format!(
+            "{} {} {} {} {}\n",
+            prefix, from.row, from.col, to.row, to.col
+        ),
+    }
+}
+
+/// `PLACE <row> <col>` from the client, or `PLACED <row> <col>` from the
+/// host, for a gate placed instead of a move.
+fn format_placement_line(prefix: &str, mv: &Move) -> String {
+    format!("{} {} {}\n", prefix, mv.to.row, mv.to.col)
 }
 
 fn set_state(state: &Arc<Mutex<SessionState>>, role: SessionRole, local_color: Color) {
@@ -472,28 +542,57 @@ fn format_time_control_line(time_control: TimeControl) -> String {
     )
 }
 
-fn parse_move_line(line: &str, prefix: &str) -> Option<(Position, Position)> {
+/// `GATES <rules>`, with the rules in `GateRules::to_notation` form, such
+/// as `GATES rb 2 p 1 0`.
+fn format_gate_rules_line(rules: GateRules) -> String {
+    format!("GATES {}\n", rules.to_notation())
+}
+
+/// `POSITION <fen>`, the position the game starts from, gates and all.
+fn format_start_position_line(fen: &str) -> String {
+    format!("POSITION {}\n", fen)
+}
+
+fn parse_start_position_line(line: &str) -> Option<String> {
+    let fen = line.strip_prefix("POSITION ")?.trim();
+    Game::from_fen(fen).ok().map(|_| fen.to_string())
+}
+
+fn parse_move_line(line: &str, prefix: &str) -> Option<Move> {
     let mut parts = line.split_whitespace();
 
     if parts.next()? != prefix {
         return None;
     }
 
-    let from_row = parts.next()?.parse().ok()?;
-    let from_col = parts.next()?.parse().ok()?;
-    let to_row = parts.next()?.parse().ok()?;
-    let to_col = parts.next()?.parse().ok()?;
-
-    Some((
-        Position {
-            row: from_row,
-            col: from_col,
-        },
-        Position {
-            row: to_row,
-            col: to_col,
-        },
-    ))
+    let from = parse_square(&mut parts)?;
+    let to = parse_square(&mut parts)?;
+
+    // Promotion moves carry the chosen piece letter as a trailing field
+    let promotion = match parts.next() {
+        Some(letter) => {
+            let mut chars = letter.chars();
+            let kind = PieceType::from_char(chars.next()?)?;
+            if chars.next().is_some() {
+                return None;
+            }
+            Some(kind)
+        }
+        None => None,
+    };
+
+    Some(Move::new(from, to).with_promotion(promotion))
+}
+
+/// Reads a `<row> <col>` pair, refusing squares off the board so a bad peer
+/// can't index past it.
+fn parse_square<'a>(parts: &mut impl Iterator<Item = &'a str>) -> Option<Position> {
+    let row: usize = parts.next()?.parse().ok()?;
+    let col: usize = parts.next()?.parse().ok()?;
+    if row >= BOARD_SIZE || col >= BOARD_SIZE {
+        return None;
+    }
+    Some(Position { row, col })
 }
 
 fn parse_time_control_line(line: &str) -> Option<TimeControl> {
@@ -508,3 +607,22 @@ fn parse_time_control_line(line: &str) -> Option<TimeControl> {
         parts.next()?.parse().ok()?,
     ))
 }
+
+fn parse_gate_rules_line(line: &str) -> Option<GateRules> {
+    GateRules::from_notation(line.strip_prefix("GATES ")?)
+}
+
+fn parse_placement_line(line: &str, prefix: &str) -> Option<Move> {
+    let mut parts = line.split_whitespace();
+
+    if parts.next()? != prefix {
+        return None;
+    }
+
+    let square = parse_square(&mut parts)?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(Move::place_gate(square))
+}
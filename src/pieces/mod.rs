@@ -1,60 +0,0 @@
-// =======================================================
-// Project: GatedChess
-// File: pieces/mod.rs
-// Description: Defines pieces used in-game.
-// Author: Seamus Daniello
-// Created: 2025-11-07
-// Last Modified: 2025-11-30
-// License: MIT
-// =======================================================
-
-pub mod piece_animations;
-pub mod piece_fsm;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum PieceType {
-    Pawn,
-    Rook,
-    Knight,
-    Bishop,
-    Queen,
-    King,
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum Color {
-    White,
-    Black,
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct PieceId(pub u32);
-
-static NEXT_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
-
-impl PieceId {
-    pub fn new() -> Self {
-        PieceId(NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
-    }
-}
-
-#[derive(Copy, Clone, Debug)]
-pub struct Piece {
-    pub id: PieceId,
-    pub kind: PieceType,
-    pub color: Color,
-}
-
-impl Piece {
-    pub fn new(kind: PieceType, color: Color) -> Piece {
-        Piece {
-            id: PieceId::new(),
-            kind,
-            color,
-        }
-    }
-
-    pub fn get_color(&self) -> Color {
-        self.color
-    }
-}